@@ -1,5 +1,6 @@
 // Analysis engine for comprehensive metrics computation
 use crate::core::evaluation::*;
+use crate::core::schema::ResponseSchema;
 use crate::utils::error::Result;
 use std::collections::HashMap;
 use regex::Regex;
@@ -26,6 +27,8 @@ impl AnalysisEngine {
         results: &[ExecutionResult],
         options: &AnalysisOptions,
     ) -> Result<AnalysisResults> {
+        let (schema_conformance, schema_conformance_by_model) = self.compute_schema_conformance(results);
+        
         let mut analysis = AnalysisResults {
             response_metrics: Vec::new(),
             similarity_matrix: Vec::new(),
@@ -33,6 +36,10 @@ impl AnalysisEngine {
             quality_indicators: Vec::new(),
             cost_breakdown: self.compute_cost_breakdown(results)?,
             performance_stats: self.compute_performance_stats(results)?,
+            tool_call_scores: self.compute_tool_call_scores(results),
+            schema_conformance,
+            schema_conformance_by_model,
+            cell_variance: self.compute_cell_variance(results),
         };
         
         // Compute response metrics
@@ -188,6 +195,8 @@ impl AnalysisEngine {
         let mut cost_by_model = HashMap::new();
         let mut input_token_cost = 0.0;
         let mut output_token_cost = 0.0;
+        let mut unpriced_models = Vec::new();
+        let mut batch_cost_estimate: Option<f64> = None;
         
         for result in results {
             // Cost by provider
@@ -197,16 +206,58 @@ impl AnalysisEngine {
             let model_key = format!("{}/{}", result.provider, result.model);
             *cost_by_model.entry(model_key).or_insert(0.0) += result.metadata.cost_usd;
             
-            // Estimate input/output token costs (rough approximation)
-            let total_tokens = result.metadata.token_count_input + result.metadata.token_count_output;
-            if total_tokens > 0 {
-                let input_ratio = result.metadata.token_count_input as f64 / total_tokens as f64;
-                input_token_cost += result.metadata.cost_usd * input_ratio;
-                output_token_cost += result.metadata.cost_usd * (1.0 - input_ratio);
+            if result.metadata.cost_unknown {
+                if !unpriced_models.contains(&model_key) {
+                    unpriced_models.push(model_key);
+                }
+                continue;
+            }
+            
+            let priced_split = result.metadata.input_cost_usd + result.metadata.output_cost_usd;
+            if priced_split > 0.0 {
+                input_token_cost += result.metadata.input_cost_usd;
+                output_token_cost += result.metadata.output_cost_usd;
+            } else {
+                // Results saved before the split was recorded: approximate by token ratio
+                let total_tokens = result.metadata.token_count_input + result.metadata.token_count_output;
+                if total_tokens > 0 {
+                    let input_ratio = result.metadata.token_count_input as f64 / total_tokens as f64;
+                    input_token_cost += result.metadata.cost_usd * input_ratio;
+                    output_token_cost += result.metadata.cost_usd * (1.0 - input_ratio);
+                }
+            }
+            
+            // The batch estimate, like the total, covers only what this run paid for.
+            // Models without a batch discount count at their regular price.
+            if result.metadata.cache_hit {
+                continue;
+            }
+            if let Some(batch_cost) = result.metadata.batch_cost_usd {
+                *batch_cost_estimate.get_or_insert(0.0) += batch_cost;
             }
         }
         
-        let total_cost = results.iter().map(|r| r.metadata.cost_usd).sum();
+        // Per-provider and per-model costs keep cache hits so comparisons don't depend on the
+        // cache; the totals only count what this run paid for
+        let total_cost: f64 = results.iter()
+            .filter(|r| !r.metadata.cache_hit)
+            .map(|r| r.metadata.cost_usd)
+            .sum();
+        let cache_savings: f64 = results.iter()
+            .filter(|r| r.metadata.cache_hit)
+            .map(|r| r.metadata.cost_usd)
+            .sum();
+        
+        // Only report a batch estimate when some model actually has a discount
+        let batch_cost_estimate = batch_cost_estimate.map(|discounted| {
+            let undiscounted: f64 = results.iter()
+                .filter(|r| r.metadata.batch_cost_usd.is_none() && !r.metadata.cache_hit)
+                .map(|r| r.metadata.cost_usd)
+                .sum();
+            discounted + undiscounted
+        });
+        
+        unpriced_models.sort();
         
         Ok(CostBreakdown {
             total_cost,
@@ -214,6 +265,9 @@ impl AnalysisEngine {
             cost_by_model,
             input_token_cost,
             output_token_cost,
+            unpriced_models,
+            batch_cost_estimate,
+            cache_savings,
         })
     }
     
@@ -227,7 +281,14 @@ impl AnalysisEngine {
             .count() as u32;
         
         let total_executions = results.len() as u32;
-        let failed_executions = total_executions - successful_executions;
+        
+        // Timeouts are kept apart from failures so slow models aren't mistaken for broken ones
+        let mut timeouts_by_model: HashMap<String, u32> = HashMap::new();
+        for result in results.iter().filter(|r| matches!(r.status, ExecutionStatus::Timeout)) {
+            *timeouts_by_model.entry(format!("{}/{}", result.provider, result.model)).or_insert(0) += 1;
+        }
+        let timed_out_executions = timeouts_by_model.values().sum::<u32>();
+        let failed_executions = total_executions - successful_executions - timed_out_executions;
         let success_rate = if total_executions > 0 {
             (successful_executions as f32 / total_executions as f32) * 100.0
         } else {
@@ -256,6 +317,50 @@ impl AnalysisEngine {
         let min_response_time = response_times.iter().min().copied().unwrap_or(0);
         let max_response_time = response_times.iter().max().copied().unwrap_or(0);
         
+        // Streaming metrics are only present for streamed, successful executions
+        let streamed: Vec<&ExecutionResult> = results.iter()
+            .filter(|r| r.metadata.time_to_first_token_ms.is_some())
+            .collect();
+        
+        let ttft_values: Vec<f64> = streamed.iter()
+            .filter_map(|r| r.metadata.time_to_first_token_ms)
+            .map(|ms| ms as f64)
+            .collect();
+        let itl_values: Vec<f64> = streamed.iter()
+            .filter_map(|r| r.metadata.inter_token_latency_ms)
+            .collect();
+        let tps_values: Vec<f64> = streamed.iter()
+            .filter_map(|r| r.metadata.tokens_per_second)
+            .collect();
+        
+        let mut streamed_by_model: HashMap<String, Vec<&ExecutionResult>> = HashMap::new();
+        for result in &streamed {
+            let model_key = format!("{}/{}", result.provider, result.model);
+            streamed_by_model.entry(model_key).or_insert_with(Vec::new).push(*result);
+        }
+        
+        let streaming_by_model = streamed_by_model.into_iter()
+            .map(|(model, model_results)| {
+                let ttft: Vec<f64> = model_results.iter()
+                    .filter_map(|r| r.metadata.time_to_first_token_ms)
+                    .map(|ms| ms as f64)
+                    .collect();
+                let itl: Vec<f64> = model_results.iter()
+                    .filter_map(|r| r.metadata.inter_token_latency_ms)
+                    .collect();
+                let tps: Vec<f64> = model_results.iter()
+                    .filter_map(|r| r.metadata.tokens_per_second)
+                    .collect();
+                
+                (model, StreamingStats {
+                    streamed_executions: model_results.len() as u32,
+                    avg_time_to_first_token: mean(&ttft).unwrap_or(0.0),
+                    avg_inter_token_latency: mean(&itl),
+                    avg_tokens_per_second: mean(&tps),
+                })
+            })
+            .collect();
+        
         Ok(PerformanceStats {
             avg_response_time,
             median_response_time,
@@ -264,9 +369,212 @@ impl AnalysisEngine {
             success_rate,
             total_executions,
             failed_executions,
+            timed_out_executions,
+            timeouts_by_model,
+            avg_time_to_first_token: mean(&ttft_values),
+            median_time_to_first_token: median(&ttft_values),
+            avg_inter_token_latency: mean(&itl_values),
+            avg_tokens_per_second: mean(&tps_values),
+            streaming_by_model,
         })
     }
     
+    /// Score tool selection and arguments per model, over executions whose prompt
+    /// declared expected tool calls. Failed executions count as making no calls.
+    fn compute_tool_call_scores(&self, results: &[ExecutionResult]) -> Vec<ToolCallScore> {
+        #[derive(Default)]
+        struct Totals {
+            executions: u32,
+            expected_calls: u32,
+            selected_calls: u32,
+            argument_score: f32,
+            exact_matches: u32,
+            unexpected_calls: u32,
+        }
+        
+        let mut by_model: HashMap<(String, String), Totals> = HashMap::new();
+        
+        for result in results.iter().filter(|r| !r.expected_tool_calls.is_empty()) {
+            let outcome = score_tool_calls(&result.expected_tool_calls, &result.tool_calls);
+            let totals = by_model.entry((result.provider.clone(), result.model.clone())).or_default();
+            
+            totals.executions += 1;
+            totals.expected_calls += result.expected_tool_calls.len() as u32;
+            totals.selected_calls += outcome.selected;
+            totals.argument_score += outcome.argument_score;
+            totals.unexpected_calls += outcome.unexpected;
+            if outcome.exact {
+                totals.exact_matches += 1;
+            }
+        }
+        
+        let mut scores: Vec<ToolCallScore> = by_model.into_iter()
+            .map(|((provider, model), totals)| ToolCallScore {
+                provider,
+                model,
+                executions: totals.executions,
+                expected_calls: totals.expected_calls,
+                tool_selection_accuracy: totals.selected_calls as f32 / totals.expected_calls as f32,
+                argument_accuracy: if totals.selected_calls > 0 {
+                    totals.argument_score / totals.selected_calls as f32
+                } else {
+                    0.0
+                },
+                exact_match_rate: totals.exact_matches as f32 / totals.executions as f32,
+                unexpected_calls: totals.unexpected_calls,
+            })
+            .collect();
+        
+        scores.sort_by(|a, b| (&a.provider, &a.model).cmp(&(&b.provider, &b.model)));
+        scores
+    }
+    
+    /// Check successful executions of prompts with a response schema, then aggregate per
+    /// model. Failed executions have no output to check and are left to the success rate.
+    fn compute_schema_conformance(&self, results: &[ExecutionResult]) -> (Vec<SchemaConformance>, Vec<SchemaConformanceRate>) {
+        #[derive(Default)]
+        struct Totals {
+            executions: u32,
+            parsed: u32,
+            valid: u32,
+            failed_paths: HashMap<String, u32>,
+        }
+        
+        // Every execution of a prompt carries the same schema, so each is compiled once
+        let mut compiled: HashMap<String, Option<ResponseSchema>> = HashMap::new();
+        let mut conformance = Vec::new();
+        let mut by_model: HashMap<(String, String), Totals> = HashMap::new();
+        
+        for result in results.iter().filter(|r| matches!(r.status, ExecutionStatus::Success)) {
+            let schema = match &result.response_schema {
+                Some(schema) => schema,
+                None => continue,
+            };
+            
+            let validator = compiled.entry(schema.to_string())
+                .or_insert_with(|| ResponseSchema::compile(schema).ok());
+            let (parses, violations) = match validator {
+                Some(validator) => {
+                    let check = validator.check(&result.output);
+                    (check.parses, check.violations)
+                },
+                None => (false, vec![SchemaViolation {
+                    path: "/".to_string(),
+                    message: "response schema is not a valid JSON Schema".to_string(),
+                }]),
+            };
+            let valid = parses && violations.is_empty();
+            
+            let totals = by_model.entry((result.provider.clone(), result.model.clone())).or_default();
+            totals.executions += 1;
+            if parses {
+                totals.parsed += 1;
+                for violation in &violations {
+                    *totals.failed_paths.entry(violation.path.clone()).or_insert(0) += 1;
+                }
+            }
+            if valid {
+                totals.valid += 1;
+            }
+            
+            conformance.push(SchemaConformance {
+                execution_id: result.id.clone(),
+                provider: result.provider.clone(),
+                model: result.model.clone(),
+                parses,
+                valid,
+                violations,
+            });
+        }
+        
+        let mut rates: Vec<SchemaConformanceRate> = by_model.into_iter()
+            .map(|((provider, model), totals)| {
+                let mut failed_paths: Vec<(String, u32)> = totals.failed_paths.into_iter().collect();
+                failed_paths.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+                
+                SchemaConformanceRate {
+                    provider,
+                    model,
+                    executions: totals.executions,
+                    parse_rate: totals.parsed as f32 / totals.executions as f32 * 100.0,
+                    valid_rate: totals.valid as f32 / totals.executions as f32 * 100.0,
+                    failed_paths: failed_paths.into_iter().map(|(path, _)| path).collect(),
+                }
+            })
+            .collect();
+        
+        rates.sort_by(|a, b| (&a.provider, &a.model).cmp(&(&b.provider, &b.model)));
+        (conformance, rates)
+    }
+    
+    /// Spread of latency, cost and quality across the repeated samples of each cell, and how
+    /// much the outputs agree with each other. Only successful samples drawn in this run are
+    /// measured: a cache hit carries the latency and output of an older call.
+    fn compute_cell_variance(&self, results: &[ExecutionResult]) -> Vec<CellVariance> {
+        let mut cells: HashMap<&str, Vec<&ExecutionResult>> = HashMap::new();
+        for result in results.iter().filter(|r| !r.cell_id.is_empty()) {
+            cells.entry(result.cell_id.as_str()).or_default().push(result);
+        }
+        
+        let mut variance: Vec<CellVariance> = cells.into_iter()
+            .filter(|(_, samples)| samples.len() > 1)
+            .map(|(cell_id, samples)| {
+                let successful: Vec<&ExecutionResult> = samples.iter()
+                    .copied()
+                    .filter(|r| matches!(r.status, ExecutionStatus::Success) && !r.metadata.cache_hit)
+                    .collect();
+                
+                let latencies: Vec<f64> = successful.iter().map(|r| r.metadata.response_time_ms as f64).collect();
+                let qualities: Vec<f64> = successful.iter()
+                    .map(|r| {
+                        let relevance = self.quality_assessor.assess_relevance(&r.output, &r.input);
+                        let accuracy = self.quality_assessor.assess_accuracy(&r.output);
+                        let helpfulness = self.quality_assessor.assess_helpfulness(&r.output);
+                        ((relevance + accuracy + helpfulness) / 3.0) as f64
+                    })
+                    .collect();
+                
+                // A cost spread over partly unpriced samples would understate the variance
+                let cost_usd = if successful.iter().any(|r| r.metadata.cost_unknown) {
+                    None
+                } else {
+                    spread(&successful.iter().map(|r| r.metadata.cost_usd).collect::<Vec<_>>())
+                };
+                
+                let mut similarities = Vec::new();
+                for (i, first) in successful.iter().enumerate() {
+                    for second in &successful[i + 1..] {
+                        similarities.push(self.similarity_calculator.calculate_similarity(
+                            &first.output,
+                            &second.output,
+                            SimilarityType::Cosine,
+                        ) as f64);
+                    }
+                }
+                
+                CellVariance {
+                    cell_id: cell_id.to_string(),
+                    resolved_prompt: samples[0].resolved_prompt.clone(),
+                    provider: samples[0].provider.clone(),
+                    model: samples[0].model.clone(),
+                    samples: samples.len() as u32,
+                    successful_samples: successful.len() as u32,
+                    latency_ms: spread(&latencies),
+                    cost_usd,
+                    quality: spread(&qualities),
+                    self_agreement: mean(&similarities).map(|m| m as f32),
+                }
+            })
+            .collect();
+        
+        // Cells of the same prompt variation sit together, in config order
+        let prompt_order = |cell: &CellVariance| cell.resolved_prompt.as_ref().map(|p| (p.prompt_index, p.variation));
+        variance.sort_by(|a, b| {
+            (prompt_order(a), &a.provider, &a.model, &a.cell_id).cmp(&(prompt_order(b), &b.provider, &b.model, &b.cell_id))
+        });
+        variance
+    }
+    
     fn count_sentences(&self, text: &str) -> usize {
         let sentence_endings = Regex::new(r"[.!?]+").unwrap();
         sentence_endings.find_iter(text).count()
@@ -331,6 +639,124 @@ impl AnalysisEngine {
     }
 }
 
+fn mean(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.iter().sum::<f64>() / values.len() as f64)
+    }
+}
+
+/// Mean, sample standard deviation and range; `None` without any values
+fn spread(values: &[f64]) -> Option<MetricSpread> {
+    let mean = mean(values)?;
+    let std_dev = if values.len() > 1 {
+        let squared: f64 = values.iter().map(|v| (v - mean).powi(2)).sum();
+        (squared / (values.len() - 1) as f64).sqrt()
+    } else {
+        0.0
+    };
+    
+    Some(MetricSpread {
+        mean,
+        std_dev,
+        min: values.iter().cloned().fold(f64::INFINITY, f64::min),
+        max: values.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+    })
+}
+
+fn median(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    
+    let mid = sorted.len() / 2;
+    if sorted.len() % 2 == 0 {
+        Some((sorted[mid - 1] + sorted[mid]) / 2.0)
+    } else {
+        Some(sorted[mid])
+    }
+}
+
+struct ToolCallOutcome {
+    selected: u32,
+    argument_score: f32,
+    unexpected: u32,
+    exact: bool,
+}
+
+/// Match each expected call to the first unused actual call with the same tool name.
+/// Order is not enforced, since models often batch independent calls arbitrarily.
+fn score_tool_calls(expected: &[ExpectedToolCall], actual: &[ToolCall]) -> ToolCallOutcome {
+    let mut used = vec![false; actual.len()];
+    let mut selected = 0;
+    let mut argument_score = 0.0;
+    let mut all_arguments_match = true;
+    
+    for expected_call in expected {
+        let matched = (0..actual.len())
+            .find(|&i| !used[i] && actual[i].name == expected_call.name);
+        
+        match matched {
+            Some(index) => {
+                used[index] = true;
+                selected += 1;
+                
+                let score = match &expected_call.arguments {
+                    Some(arguments) if expected_call.exact_arguments => {
+                        if *arguments == actual[index].arguments { 1.0 } else { 0.0 }
+                    },
+                    Some(arguments) => argument_match(arguments, &actual[index].arguments),
+                    None => 1.0,
+                };
+                argument_score += score;
+                all_arguments_match &= score >= 1.0;
+            },
+            None => all_arguments_match = false,
+        }
+    }
+    
+    let unexpected = used.iter().filter(|u| !**u).count() as u32;
+    
+    ToolCallOutcome {
+        selected,
+        argument_score,
+        unexpected,
+        exact: all_arguments_match && unexpected == 0,
+    }
+}
+
+/// Share of the expected top-level arguments the actual call matched. Nested objects
+/// match when every expected field is present with an equal value.
+fn argument_match(expected: &serde_json::Value, actual: &serde_json::Value) -> f32 {
+    match expected.as_object() {
+        Some(fields) if !fields.is_empty() => {
+            let matched = fields.iter()
+                .filter(|(key, value)| actual.get(key.as_str()).is_some_and(|a| json_contains(value, a)))
+                .count();
+            matched as f32 / fields.len() as f32
+        },
+        Some(_) => 1.0,
+        None => if expected == actual { 1.0 } else { 0.0 },
+    }
+}
+
+fn json_contains(expected: &serde_json::Value, actual: &serde_json::Value) -> bool {
+    match (expected, actual) {
+        (serde_json::Value::Object(expected), serde_json::Value::Object(actual)) => {
+            expected.iter().all(|(key, value)| actual.get(key).is_some_and(|a| json_contains(value, a)))
+        },
+        // Integers and floats compare by value (1 == 1.0)
+        (serde_json::Value::Number(expected), serde_json::Value::Number(actual)) => {
+            expected.as_f64() == actual.as_f64()
+        },
+        _ => expected == actual,
+    }
+}
+
 // Helper structs for analysis components
 pub struct SentimentAnalyzer;
 
@@ -538,7 +964,7 @@ impl ContentAnalyzer {
         
         // Grammar score based on basic indicators
         let grammar_score = if text.matches('.').count() > 0 
-            && text.chars().next().map_or(false, |c| c.is_uppercase()) {
+            && text.chars().next().is_some_and(|c| c.is_uppercase()) {
             80.0
         } else {
             60.0
@@ -661,4 +1087,185 @@ impl QualityAssessor {
         
         score.max(0.0).min(100.0)
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    
+    fn call(name: &str, arguments: serde_json::Value) -> ToolCall {
+        ToolCall { id: None, name: name.to_string(), arguments }
+    }
+    
+    fn expect(name: &str, arguments: Option<serde_json::Value>, exact_arguments: bool) -> ExpectedToolCall {
+        ExpectedToolCall { name: name.to_string(), arguments, exact_arguments }
+    }
+    
+    #[test]
+    fn argument_match_scores_share_of_expected_fields() {
+        let actual = json!({"city": "Paris", "unit": "celsius", "days": 3});
+        assert_eq!(argument_match(&json!({"city": "Paris", "unit": "celsius"}), &actual), 1.0);
+        assert_eq!(argument_match(&json!({"city": "Paris", "unit": "fahrenheit"}), &actual), 0.5);
+        assert_eq!(argument_match(&json!({"country": "France"}), &actual), 0.0);
+    }
+    
+    #[test]
+    fn empty_expected_arguments_always_match() {
+        assert_eq!(argument_match(&json!({}), &json!({})), 1.0);
+        assert_eq!(argument_match(&json!({}), &json!({"city": "Paris"})), 1.0);
+        assert_eq!(argument_match(&json!({}), &json!("not an object")), 1.0);
+    }
+    
+    #[test]
+    fn argument_match_compares_numbers_by_value_and_nested_objects_by_subset() {
+        assert_eq!(argument_match(&json!({"days": 3}), &json!({"days": 3.0})), 1.0);
+        
+        let actual = json!({"filter": {"min": 1, "max": 5, "sort": "asc"}});
+        assert_eq!(argument_match(&json!({"filter": {"min": 1}}), &actual), 1.0);
+        assert_eq!(argument_match(&json!({"filter": {"min": 2}}), &actual), 0.0);
+    }
+    
+    #[test]
+    fn non_object_arguments_must_be_equal() {
+        assert_eq!(argument_match(&json!("raw"), &json!("raw")), 1.0);
+        assert_eq!(argument_match(&json!("raw"), &json!({"raw": true})), 0.0);
+        // Arrays are compared whole, in order
+        assert_eq!(argument_match(&json!({"ids": [1, 2]}), &json!({"ids": [2, 1]})), 0.0);
+    }
+    
+    #[test]
+    fn tool_calls_match_by_name_in_any_order() {
+        let expected = [expect("search", None, false), expect("weather", Some(json!({"city": "Paris"})), false)];
+        let actual = [call("weather", json!({"city": "Paris"})), call("search", json!({"q": "x"}))];
+        
+        let outcome = score_tool_calls(&expected, &actual);
+        assert_eq!(outcome.selected, 2);
+        assert_eq!(outcome.argument_score, 2.0);
+        assert_eq!(outcome.unexpected, 0);
+        assert!(outcome.exact);
+    }
+    
+    #[test]
+    fn missing_and_unexpected_calls_are_not_exact() {
+        let expected = [expect("weather", None, false), expect("search", None, false)];
+        let outcome = score_tool_calls(&expected, &[call("weather", json!({})), call("weather", json!({}))]);
+        assert_eq!(outcome.selected, 1);
+        assert_eq!(outcome.unexpected, 1);
+        assert!(!outcome.exact);
+        
+        let outcome = score_tool_calls(&expected, &[]);
+        assert_eq!(outcome.selected, 0);
+        assert_eq!(outcome.argument_score, 0.0);
+        assert!(!outcome.exact);
+    }
+    
+    #[test]
+    fn no_expected_calls_and_none_made_is_exact() {
+        let outcome = score_tool_calls(&[], &[]);
+        assert_eq!(outcome.selected, 0);
+        assert!(outcome.exact);
+        
+        assert!(!score_tool_calls(&[], &[call("weather", json!({}))]).exact);
+    }
+    
+    #[test]
+    fn exact_arguments_reject_extra_fields() {
+        let expected = [expect("weather", Some(json!({"city": "Paris"})), true)];
+        
+        let outcome = score_tool_calls(&expected, &[call("weather", json!({"city": "Paris"}))]);
+        assert_eq!(outcome.argument_score, 1.0);
+        assert!(outcome.exact);
+        
+        let outcome = score_tool_calls(&expected, &[call("weather", json!({"city": "Paris", "unit": "c"}))]);
+        assert_eq!(outcome.selected, 1);
+        assert_eq!(outcome.argument_score, 0.0);
+        assert!(!outcome.exact);
+    }
+    
+    #[test]
+    fn partial_arguments_are_not_exact() {
+        let expected = [expect("weather", Some(json!({"city": "Paris", "unit": "celsius"})), false)];
+        let outcome = score_tool_calls(&expected, &[call("weather", json!({"city": "Paris"}))]);
+        assert_eq!(outcome.argument_score, 0.5);
+        assert!(!outcome.exact);
+    }
+    
+    #[test]
+    fn spread_of_nothing_is_none() {
+        assert!(spread(&[]).is_none());
+        assert!(median(&[]).is_none());
+    }
+    
+    #[test]
+    fn single_sample_has_no_deviation() {
+        let spread = spread(&[4.2]).unwrap();
+        assert_eq!(spread.mean, 4.2);
+        assert_eq!(spread.std_dev, 0.0);
+        assert_eq!(spread.min, 4.2);
+        assert_eq!(spread.max, 4.2);
+        assert_eq!(median(&[4.2]), Some(4.2));
+    }
+    
+    #[test]
+    fn spread_uses_sample_standard_deviation() {
+        let spread = spread(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]).unwrap();
+        assert_eq!(spread.mean, 5.0);
+        // Population deviation would be exactly 2.0
+        assert!((spread.std_dev - (32.0f64 / 7.0).sqrt()).abs() < 1e-12);
+        assert_eq!(spread.min, 2.0);
+        assert_eq!(spread.max, 9.0);
+    }
+    
+    #[test]
+    fn identical_samples_have_no_deviation() {
+        assert_eq!(spread(&[3.0, 3.0, 3.0]).unwrap().std_dev, 0.0);
+    }
+    
+    #[test]
+    fn median_handles_odd_and_even_counts_in_any_order() {
+        assert_eq!(median(&[5.0, 1.0, 3.0]), Some(3.0));
+        assert_eq!(median(&[4.0, 1.0, 3.0, 2.0]), Some(2.5));
+        assert_eq!(median(&[-1.0, 1.0]), Some(0.0));
+    }
+    
+    fn sample(repetition: u32, output: &str, response_time_ms: u64, cache_hit: bool) -> ExecutionResult {
+        serde_json::from_value(json!({
+            "id": format!("exec-{}", repetition),
+            "prompt_id": "prompt",
+            "provider": "openai",
+            "model": "gpt-4o",
+            "input": "Name a colour",
+            "output": output,
+            "metadata": {
+                "response_time_ms": response_time_ms,
+                "token_count_input": 3,
+                "token_count_output": 1,
+                "cost_usd": 0.001,
+                "timestamp": "2026-10-01T00:00:00Z",
+                "cache_hit": cache_hit,
+            },
+            "status": "Success",
+            "cell_id": "cell",
+            "repetition": repetition,
+        })).unwrap()
+    }
+    
+    #[test]
+    fn cell_variance_leaves_out_cache_hits() {
+        let results = [
+            sample(0, "red", 9000, true),
+            sample(1, "blue", 100, false),
+            sample(2, "blue", 300, false),
+        ];
+        let variance = AnalysisEngine::new().compute_cell_variance(&results);
+        assert_eq!(variance.len(), 1);
+        assert_eq!(variance[0].samples, 3);
+        assert_eq!(variance[0].successful_samples, 2);
+        
+        let latency = variance[0].latency_ms.as_ref().unwrap();
+        assert_eq!(latency.mean, 200.0);
+        assert_eq!(latency.max, 300.0);
+        assert_eq!(variance[0].self_agreement, Some(1.0));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,409 @@
+// HTTP layer shared by the network providers, with record/replay cassettes
+use crate::utils::error::{Result, EvalError};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How provider HTTP traffic is handled for a run
+pub enum HttpMode {
+    /// Talk to the network directly
+    Live,
+    /// Talk to the network and write every exchange to the cassette
+    Record(Cassette),
+    /// Serve exchanges from the cassette; the network is never touched
+    Replay(Cassette),
+}
+
+/// Cloneable HTTP client handed to every provider
+#[derive(Clone)]
+pub struct HttpClient {
+    client: reqwest::Client,
+    mode: Arc<HttpMode>,
+}
+
+impl HttpClient {
+    pub fn live() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            mode: Arc::new(HttpMode::Live),
+        }
+    }
+    
+    pub fn record(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        
+        Ok(Self {
+            client: reqwest::Client::new(),
+            mode: Arc::new(HttpMode::Record(Cassette::new(dir))),
+        })
+    }
+    
+    pub fn replay(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        if !dir.is_dir() {
+            return Err(EvalError::ReplayError(
+                format!("cassette directory {} does not exist", dir.display())
+            ));
+        }
+        
+        Ok(Self {
+            client: reqwest::Client::new(),
+            mode: Arc::new(HttpMode::Replay(Cassette::new(dir))),
+        })
+    }
+    
+    pub fn is_replay(&self) -> bool {
+        matches!(*self.mode, HttpMode::Replay(_))
+    }
+    
+    /// Requests that had no recorded match during replay
+    pub async fn replay_misses(&self) -> Vec<String> {
+        match &*self.mode {
+            HttpMode::Replay(cassette) => cassette.misses.lock().await.clone(),
+            _ => Vec::new(),
+        }
+    }
+    
+    /// POST a JSON body. Headers are sent but never recorded, so API keys stay out of cassettes.
+    pub async fn post_json(&self, url: &str, headers: &[(&str, &str)], body: &serde_json::Value) -> Result<HttpResponse> {
+        self.exchange("POST", url, headers, body).await
+    }
+    
+    /// GET a JSON resource, such as a model listing
+    pub async fn get_json(&self, url: &str, headers: &[(&str, &str)]) -> Result<HttpResponse> {
+        self.exchange("GET", url, headers, &serde_json::Value::Null).await
+    }
+    
+    async fn exchange(&self, method: &str, url: &str, headers: &[(&str, &str)], body: &serde_json::Value) -> Result<HttpResponse> {
+        let key = interaction_key(method, url, body);
+        
+        match &*self.mode {
+            HttpMode::Live => self.send(method, url, headers, body).await,
+            HttpMode::Record(cassette) => {
+                // Recording buffers the full body, so streamed latencies are not meaningful while recording
+                let response = self.send(method, url, headers, body).await?;
+                let status = response.status;
+                let retry_after = response.retry_after;
+                let bytes = response.bytes().await?;
+                
+                cassette.record(&key, method, url, body, RecordedResponse {
+                    status,
+                    body: String::from_utf8_lossy(&bytes).to_string(),
+                    retry_after_secs: retry_after.map(|d| d.as_secs_f64()),
+                }).await?;
+                
+                Ok(HttpResponse::buffered(status, bytes, retry_after))
+            }
+            HttpMode::Replay(cassette) => {
+                let recorded = cassette.replay(&key, method, url).await?;
+                let retry_after = recorded.retry_after_secs.map(Duration::from_secs_f64);
+                Ok(HttpResponse::buffered(recorded.status, recorded.body.into_bytes(), retry_after))
+            }
+        }
+    }
+    
+    async fn send(&self, method: &str, url: &str, headers: &[(&str, &str)], body: &serde_json::Value) -> Result<HttpResponse> {
+        let mut request = match method {
+            "GET" => self.client.get(url),
+            _ => self.client
+                .post(url)
+                .header("Content-Type", "application/json")
+                .json(body),
+        };
+        
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        
+        let response = request
+            .send()
+            .await
+            .map_err(|e| EvalError::NetworkError(e.to_string()))?;
+        
+        // Only the delay-seconds form of Retry-After is used by the provider APIs
+        let retry_after = response.headers()
+            .get("retry-after")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<f64>().ok())
+            .map(Duration::from_secs_f64);
+        
+        Ok(HttpResponse {
+            status: response.status().as_u16(),
+            retry_after,
+            body: ResponseBody::Live(response),
+        })
+    }
+}
+
+/// A provider response, either live from the network or served from memory
+pub struct HttpResponse {
+    status: u16,
+    retry_after: Option<Duration>,
+    body: ResponseBody,
+}
+
+enum ResponseBody {
+    Live(reqwest::Response),
+    Buffered(VecDeque<Vec<u8>>),
+}
+
+impl HttpResponse {
+    fn buffered(status: u16, bytes: Vec<u8>, retry_after: Option<Duration>) -> Self {
+        Self {
+            status,
+            retry_after,
+            body: ResponseBody::Buffered(VecDeque::from([bytes])),
+        }
+    }
+    
+    /// A body that arrives in the given chunks, as a live stream would
+    #[cfg(test)]
+    pub(crate) fn chunked(status: u16, chunks: Vec<Vec<u8>>) -> Self {
+        Self {
+            status,
+            retry_after: None,
+            body: ResponseBody::Buffered(chunks.into()),
+        }
+    }
+    
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+    
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.retry_after
+    }
+    
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+    
+    /// Next chunk of the body; buffered bodies are returned as a single chunk
+    pub async fn chunk(&mut self) -> Result<Option<Vec<u8>>> {
+        match &mut self.body {
+            ResponseBody::Live(response) => Ok(response.chunk().await
+                .map_err(|e| EvalError::NetworkError(e.to_string()))?
+                .map(|bytes| bytes.to_vec())),
+            ResponseBody::Buffered(chunks) => Ok(chunks.pop_front()),
+        }
+    }
+    
+    pub async fn bytes(mut self) -> Result<Vec<u8>> {
+        let mut body = Vec::new();
+        while let Some(chunk) = self.chunk().await? {
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body)
+    }
+    
+    pub async fn text(self) -> Result<String> {
+        Ok(String::from_utf8_lossy(&self.bytes().await?).to_string())
+    }
+    
+    pub async fn json(self) -> Result<serde_json::Value> {
+        let bytes = self.bytes().await?;
+        serde_json::from_slice(&bytes)
+            .map_err(|e| EvalError::ProviderError(format!("Invalid JSON response: {}", e)))
+    }
+}
+
+/// A directory of recorded exchanges, one file per distinct request
+pub struct Cassette {
+    dir: PathBuf,
+    entries: tokio::sync::Mutex<HashMap<String, CassetteEntry>>,
+    served: tokio::sync::Mutex<HashMap<String, usize>>,
+    misses: tokio::sync::Mutex<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CassetteEntry {
+    method: String,
+    url: String,
+    body: serde_json::Value,
+    /// Responses in the order they were recorded; identical requests replay them in turn
+    responses: Vec<RecordedResponse>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedResponse {
+    status: u16,
+    body: String,
+    #[serde(default)]
+    retry_after_secs: Option<f64>,
+}
+
+impl Cassette {
+    fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            entries: tokio::sync::Mutex::new(HashMap::new()),
+            served: tokio::sync::Mutex::new(HashMap::new()),
+            misses: tokio::sync::Mutex::new(Vec::new()),
+        }
+    }
+    
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+    
+    /// Recording into an existing cassette refreshes it: the first response recorded for a
+    /// request in this run replaces the ones on disk, and later identical requests in the
+    /// same run are appended after it. Requests this run doesn't make keep their recordings.
+    async fn record(&self, key: &str, method: &str, url: &str, body: &serde_json::Value, response: RecordedResponse) -> Result<()> {
+        let mut entries = self.entries.lock().await;
+        let entry = entries.entry(key.to_string()).or_insert_with(|| CassetteEntry {
+            method: method.to_string(),
+            url: redact_url(url),
+            body: body.clone(),
+            responses: Vec::new(),
+        });
+        entry.responses.push(response);
+        
+        let contents = serde_json::to_string_pretty(entry)?;
+        tokio::fs::write(self.entry_path(key), contents).await?;
+        Ok(())
+    }
+    
+    async fn replay(&self, key: &str, method: &str, url: &str) -> Result<RecordedResponse> {
+        let mut entries = self.entries.lock().await;
+        
+        if !entries.contains_key(key) {
+            match load_entry(&self.entry_path(key)).await? {
+                Some(entry) => {
+                    entries.insert(key.to_string(), entry);
+                }
+                None => {
+                    let request = format!("{} {}", method, redact_url(url));
+                    self.misses.lock().await.push(request.clone());
+                    return Err(EvalError::ReplayError(format!(
+                        "no recorded response for {} in cassette {}", request, self.dir.display()
+                    )));
+                }
+            }
+        }
+        
+        let entry = &entries[key];
+        let mut served = self.served.lock().await;
+        let index = served.entry(key.to_string()).or_insert(0);
+        
+        // Once the recorded responses are used up, keep serving the last one
+        let response = entry.responses.get(*index)
+            .or_else(|| entry.responses.last())
+            .cloned()
+            .ok_or_else(|| EvalError::ReplayError(format!("cassette entry {} has no responses", key)))?;
+        *index += 1;
+        
+        Ok(response)
+    }
+}
+
+async fn load_entry(path: &Path) -> Result<Option<CassetteEntry>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    
+    let contents = tokio::fs::read_to_string(path).await?;
+    let entry = serde_json::from_str(&contents)
+        .map_err(|e| EvalError::ReplayError(format!("invalid cassette file {}: {}", path.display(), e)))?;
+    Ok(Some(entry))
+}
+
+/// Requests are matched on method, URL and JSON body (null for GET)
+fn interaction_key(method: &str, url: &str, body: &serde_json::Value) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(redact_url(url).as_bytes());
+    hasher.update(b"\n");
+    hasher.update(body.to_string().as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Drop credentials passed as query parameters
+fn redact_url(url: &str) -> String {
+    match url.split_once('?') {
+        Some((base, query)) => {
+            let kept: Vec<&str> = query.split('&')
+                .filter(|param| !param.starts_with("key=") && !param.starts_with("api_key="))
+                .collect();
+            if kept.is_empty() {
+                base.to_string()
+            } else {
+                format!("{}?{}", base, kept.join("&"))
+            }
+        }
+        None => url.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn response(body: &str) -> RecordedResponse {
+        RecordedResponse { status: 200, body: body.to_string(), retry_after_secs: None }
+    }
+    
+    #[test]
+    fn redact_url_drops_key_parameters() {
+        assert_eq!(redact_url("https://api.example.com/v1/models?key=secret"), "https://api.example.com/v1/models");
+        assert_eq!(redact_url("https://api.example.com/v1?api_key=secret&alt=sse"), "https://api.example.com/v1?alt=sse");
+        assert_eq!(redact_url("https://api.example.com/v1?alt=sse"), "https://api.example.com/v1?alt=sse");
+        assert_eq!(redact_url("https://api.example.com/v1"), "https://api.example.com/v1");
+    }
+    
+    #[test]
+    fn redact_url_keeps_parameters_that_only_contain_key() {
+        assert_eq!(redact_url("https://api.example.com/v1?monkey=1"), "https://api.example.com/v1?monkey=1");
+    }
+    
+    #[test]
+    fn interaction_key_ignores_credentials_in_the_url() {
+        let body = serde_json::json!({"model": "m"});
+        assert_eq!(
+            interaction_key("POST", "https://api.example.com/v1?key=first", &body),
+            interaction_key("POST", "https://api.example.com/v1?key=second", &body),
+        );
+    }
+    
+    #[test]
+    fn interaction_key_distinguishes_method_url_and_body() {
+        let body = serde_json::json!({"model": "m"});
+        let key = interaction_key("POST", "https://api.example.com/v1", &body);
+        assert_ne!(key, interaction_key("GET", "https://api.example.com/v1", &body));
+        assert_ne!(key, interaction_key("POST", "https://api.example.com/v2", &body));
+        assert_ne!(key, interaction_key("POST", "https://api.example.com/v1", &serde_json::json!({"model": "n"})));
+    }
+    
+    #[tokio::test]
+    async fn recording_into_an_existing_cassette_replaces_earlier_responses() {
+        let dir = tempfile::tempdir().unwrap();
+        let body = serde_json::json!({"model": "m"});
+        let key = interaction_key("POST", "https://api.example.com/v1", &body);
+        
+        let first = Cassette::new(dir.path().to_path_buf());
+        first.record(&key, "POST", "https://api.example.com/v1", &body, response("stale")).await.unwrap();
+        
+        let second = Cassette::new(dir.path().to_path_buf());
+        second.record(&key, "POST", "https://api.example.com/v1", &body, response("one")).await.unwrap();
+        second.record(&key, "POST", "https://api.example.com/v1", &body, response("two")).await.unwrap();
+        
+        let replay = Cassette::new(dir.path().to_path_buf());
+        assert_eq!(replay.replay(&key, "POST", "https://api.example.com/v1").await.unwrap().body, "one");
+        assert_eq!(replay.replay(&key, "POST", "https://api.example.com/v1").await.unwrap().body, "two");
+        assert_eq!(replay.replay(&key, "POST", "https://api.example.com/v1").await.unwrap().body, "two");
+    }
+    
+    #[tokio::test]
+    async fn replay_miss_is_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let cassette = Cassette::new(dir.path().to_path_buf());
+        
+        assert!(cassette.replay("missing", "GET", "https://api.example.com/v1/models?key=secret").await.is_err());
+        assert_eq!(*cassette.misses.lock().await, vec!["GET https://api.example.com/v1/models".to_string()]);
+    }
+}
\ No newline at end of file
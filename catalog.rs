@@ -0,0 +1,133 @@
+// On-disk catalog of the models each provider offers
+use crate::core::providers::ProviderManager;
+use crate::utils::config::ProviderInfo;
+use crate::utils::error::{Result, EvalError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Entries older than this are refreshed before the wizard offers them
+pub const CATALOG_MAX_AGE_HOURS: i64 = 24;
+
+/// Models discovered through each provider's model-listing endpoint, kept in
+/// `~/.evaleds/model_catalog.json` and merged with the configured `available_models`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModelCatalog {
+    providers: HashMap<String, CatalogEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogEntry {
+    pub discovered: Vec<String>,
+    pub refreshed_at: DateTime<Utc>,
+    /// Error from the last refresh; `discovered` keeps the previous successful listing
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// A model as offered to the user, with where it came from
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatalogModel {
+    pub name: String,
+    pub configured: bool,
+    pub discovered: bool,
+    pub is_default: bool,
+}
+
+impl ModelCatalog {
+    pub fn path() -> Result<PathBuf> {
+        let home_dir = dirs::home_dir()
+            .ok_or_else(|| EvalError::ConfigError("Could not find home directory".to_string()))?;
+        
+        Ok(home_dir.join(".evaleds").join("model_catalog.json"))
+    }
+    
+    /// A missing catalog is treated as empty
+    pub async fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        
+        let contents = tokio::fs::read_to_string(&path).await?;
+        serde_json::from_str(&contents)
+            .map_err(|e| EvalError::ConfigError(format!("invalid model catalog {}: {}", path.display(), e)))
+    }
+    
+    pub async fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        
+        tokio::fs::write(&path, serde_json::to_string_pretty(self)?).await?;
+        Ok(())
+    }
+    
+    pub fn entry(&self, provider: &str) -> Option<&CatalogEntry> {
+        self.providers.get(provider)
+    }
+    
+    pub fn is_stale(&self, provider: &str) -> bool {
+        match self.providers.get(provider) {
+            Some(entry) => Utc::now() - entry.refreshed_at > chrono::Duration::hours(CATALOG_MAX_AGE_HOURS),
+            None => true,
+        }
+    }
+    
+    /// Query the provider's model-listing endpoint and store the result. On failure the
+    /// previous listing and its timestamp are kept, so the next lookup tries again.
+    /// Providers without a listing endpoint get an empty entry.
+    pub async fn refresh(&mut self, manager: &ProviderManager, provider: &str) -> Result<()> {
+        match manager.list_provider_models(provider).await {
+            Ok(listed) => {
+                let mut discovered = listed.unwrap_or_default();
+                discovered.sort();
+                discovered.dedup();
+                
+                self.providers.insert(provider.to_string(), CatalogEntry {
+                    discovered,
+                    refreshed_at: Utc::now(),
+                    error: None,
+                });
+                Ok(())
+            }
+            Err(e) => {
+                if let Some(entry) = self.providers.get_mut(provider) {
+                    entry.error = Some(e.to_string());
+                }
+                Err(e)
+            }
+        }
+    }
+    
+    /// Configured models first, in config order, followed by discovered models not in config
+    pub fn models(&self, provider: &str, info: &ProviderInfo) -> Vec<CatalogModel> {
+        let discovered: &[String] = self.providers.get(provider)
+            .map(|entry| entry.discovered.as_slice())
+            .unwrap_or(&[]);
+        
+        let mut models: Vec<CatalogModel> = info.available_models.iter()
+            .map(|name| CatalogModel {
+                name: name.clone(),
+                configured: true,
+                discovered: discovered.contains(name),
+                is_default: *name == info.default_model,
+            })
+            .collect();
+        
+        for name in discovered {
+            if !info.available_models.contains(name) {
+                models.push(CatalogModel {
+                    name: name.clone(),
+                    configured: false,
+                    discovered: true,
+                    is_default: *name == info.default_model,
+                });
+            }
+        }
+        
+        models
+    }
+}
\ No newline at end of file
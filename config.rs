@@ -9,6 +9,9 @@ pub struct EvalEdsConfig {
     pub providers: HashMap<String, ProviderInfo>,
     pub defaults: DefaultSettings,
     pub analysis: AnalysisSettings,
+    /// Credential profile providers authenticate with; unset reads API keys from environment variables
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credential_profile: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,14 +19,100 @@ pub struct ProviderInfo {
     pub available_models: Vec<String>,
     pub default_model: String,
     pub pricing: HashMap<String, ModelPricing>,
+    /// Price for models missing from `pricing`; leave unset to mark their cost as unknown
+    #[serde(default)]
+    pub default_pricing: Option<ModelPricing>,
+    /// Tokenizers keyed by model, used to count tokens when the provider doesn't report usage
+    #[serde(default)]
+    pub tokenizers: HashMap<String, TokenizerConfig>,
+    #[serde(default)]
+    pub default_tokenizer: Option<TokenizerConfig>,
     pub rate_limits: RateLimits,
     pub enabled: bool,
+    #[serde(default)]
+    pub kind: ProviderKind,
+    /// API root for openai-compatible providers, e.g. `http://localhost:8000/v1`
+    #[serde(default)]
+    pub base_url: Option<String>,
+    /// Name of the environment variable holding the API key
+    #[serde(default)]
+    pub api_key_env: Option<String>,
+    /// Extra headers sent with every request
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Executable for process providers, spoken to over JSON lines on stdin/stdout
+    #[serde(default)]
+    pub command: Option<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Fixture file for mock providers (JSON)
+    #[serde(default)]
+    pub fixtures: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProviderKind {
+    /// One of the providers shipped with EvalEds (openai, anthropic, google, local)
+    #[default]
+    Builtin,
+    /// Any server speaking the OpenAI chat completions API (vLLM, llama.cpp, LM Studio, gateways)
+    OpenaiCompatible,
+    /// A user-supplied executable speaking the JSON-lines plugin protocol
+    Process,
+    /// Scripted responses from a fixture file, for offline runs and CI
+    Mock,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelPricing {
     pub input_price_per_1k: f64,
     pub output_price_per_1k: f64,
+    /// Price for prompt tokens served from the provider's prompt cache
+    #[serde(default)]
+    pub cached_input_price_per_1k: Option<f64>,
+    /// Price for prompt tokens written to the provider's prompt cache (Anthropic bills these at a premium)
+    #[serde(default)]
+    pub cache_write_price_per_1k: Option<f64>,
+    /// Fractional discount for the provider's batch API (0.5 = 50% off)
+    #[serde(default)]
+    pub batch_discount: Option<f64>,
+}
+
+impl ModelPricing {
+    pub fn new(input_price_per_1k: f64, output_price_per_1k: f64) -> Self {
+        Self {
+            input_price_per_1k,
+            output_price_per_1k,
+            cached_input_price_per_1k: None,
+            cache_write_price_per_1k: None,
+            batch_discount: None,
+        }
+    }
+    
+    pub fn with_cache_write_price(mut self, cache_write_price_per_1k: f64) -> Self {
+        self.cache_write_price_per_1k = Some(cache_write_price_per_1k);
+        self
+    }
+    
+    /// Zero pricing for local and self-hosted models
+    pub fn free() -> Self {
+        Self::new(0.0, 0.0)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenizerConfig {
+    pub kind: TokenizerKind,
+    /// Vocab file: a tiktoken rank file for `bpe`, an exported `.vocab` file for `sentencepiece`
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TokenizerKind {
+    Bpe,
+    Sentencepiece,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,6 +128,27 @@ pub struct DefaultSettings {
     pub timeout_seconds: u64,
     pub max_concurrent: u32,
     pub retry_attempts: u32,
+    /// Serve identical requests from the local response cache; `run --no-cache` turns it off for one run
+    #[serde(default = "default_response_cache")]
+    pub response_cache: bool,
+    /// How long cached responses are served before being fetched again; 0 keeps them forever
+    #[serde(default = "default_cache_ttl_hours")]
+    pub cache_ttl_hours: u64,
+    /// Runs kept per evaluation; the oldest are dropped once a new run finishes. 0 keeps them all
+    #[serde(default = "default_runs_kept")]
+    pub runs_kept: u32,
+}
+
+fn default_response_cache() -> bool {
+    true
+}
+
+fn default_cache_ttl_hours() -> u64 {
+    24 * 7
+}
+
+fn default_runs_kept() -> u32 {
+    50
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -64,25 +174,26 @@ impl Default for EvalEdsConfig {
             default_model: "gpt-4-turbo".to_string(),
             pricing: {
                 let mut pricing = HashMap::new();
-                pricing.insert("gpt-4".to_string(), ModelPricing {
-                    input_price_per_1k: 0.03,
-                    output_price_per_1k: 0.06,
-                });
-                pricing.insert("gpt-4-turbo".to_string(), ModelPricing {
-                    input_price_per_1k: 0.01,
-                    output_price_per_1k: 0.03,
-                });
-                pricing.insert("gpt-3.5-turbo".to_string(), ModelPricing {
-                    input_price_per_1k: 0.001,
-                    output_price_per_1k: 0.002,
-                });
+                pricing.insert("gpt-4".to_string(), ModelPricing::new(0.03, 0.06));
+                pricing.insert("gpt-4-turbo".to_string(), ModelPricing::new(0.01, 0.03));
+                pricing.insert("gpt-3.5-turbo".to_string(), ModelPricing::new(0.001, 0.002));
                 pricing
             },
+            default_pricing: None,
+            tokenizers: HashMap::new(),
+            default_tokenizer: None,
             rate_limits: RateLimits {
                 requests_per_minute: 3500,
                 tokens_per_minute: 90000,
             },
             enabled: true,
+            kind: ProviderKind::Builtin,
+            base_url: None,
+            api_key_env: None,
+            headers: HashMap::new(),
+            command: None,
+            args: Vec::new(),
+            fixtures: None,
         });
         
         // Anthropic configuration
@@ -95,25 +206,26 @@ impl Default for EvalEdsConfig {
             default_model: "claude-3-sonnet-20240229".to_string(),
             pricing: {
                 let mut pricing = HashMap::new();
-                pricing.insert("claude-3-opus-20240229".to_string(), ModelPricing {
-                    input_price_per_1k: 0.015,
-                    output_price_per_1k: 0.075,
-                });
-                pricing.insert("claude-3-sonnet-20240229".to_string(), ModelPricing {
-                    input_price_per_1k: 0.003,
-                    output_price_per_1k: 0.015,
-                });
-                pricing.insert("claude-3-haiku-20240307".to_string(), ModelPricing {
-                    input_price_per_1k: 0.00025,
-                    output_price_per_1k: 0.00125,
-                });
+                pricing.insert("claude-3-opus-20240229".to_string(), ModelPricing::new(0.015, 0.075).with_cache_write_price(0.01875));
+                pricing.insert("claude-3-sonnet-20240229".to_string(), ModelPricing::new(0.003, 0.015).with_cache_write_price(0.00375));
+                pricing.insert("claude-3-haiku-20240307".to_string(), ModelPricing::new(0.00025, 0.00125).with_cache_write_price(0.0003125));
                 pricing
             },
+            default_pricing: None,
+            tokenizers: HashMap::new(),
+            default_tokenizer: None,
             rate_limits: RateLimits {
                 requests_per_minute: 1000,
                 tokens_per_minute: 100000,
             },
             enabled: true,
+            kind: ProviderKind::Builtin,
+            base_url: None,
+            api_key_env: None,
+            headers: HashMap::new(),
+            command: None,
+            args: Vec::new(),
+            fixtures: None,
         });
         
         // Google configuration
@@ -126,25 +238,26 @@ impl Default for EvalEdsConfig {
             default_model: "gemini-1.5-pro".to_string(),
             pricing: {
                 let mut pricing = HashMap::new();
-                pricing.insert("gemini-1.5-pro".to_string(), ModelPricing {
-                    input_price_per_1k: 0.0035,
-                    output_price_per_1k: 0.0105,
-                });
-                pricing.insert("gemini-1.5-flash".to_string(), ModelPricing {
-                    input_price_per_1k: 0.00035,
-                    output_price_per_1k: 0.00105,
-                });
-                pricing.insert("gemini-pro".to_string(), ModelPricing {
-                    input_price_per_1k: 0.0005,
-                    output_price_per_1k: 0.0015,
-                });
+                pricing.insert("gemini-1.5-pro".to_string(), ModelPricing::new(0.0035, 0.0105));
+                pricing.insert("gemini-1.5-flash".to_string(), ModelPricing::new(0.00035, 0.00105));
+                pricing.insert("gemini-pro".to_string(), ModelPricing::new(0.0005, 0.0015));
                 pricing
             },
+            default_pricing: None,
+            tokenizers: HashMap::new(),
+            default_tokenizer: None,
             rate_limits: RateLimits {
                 requests_per_minute: 60,
                 tokens_per_minute: 100000,
             },
             enabled: true,
+            kind: ProviderKind::Builtin,
+            base_url: None,
+            api_key_env: None,
+            headers: HashMap::new(),
+            command: None,
+            args: Vec::new(),
+            fixtures: None,
         });
         
         // Local provider configuration
@@ -156,12 +269,47 @@ impl Default for EvalEdsConfig {
                 "vicuna".to_string(),
             ],
             default_model: "llama2".to_string(),
-            pricing: HashMap::new(), // Local models are free
+            pricing: HashMap::new(),
+            default_pricing: Some(ModelPricing::free()), // Local models are free
+            tokenizers: HashMap::new(),
+            default_tokenizer: None,
             rate_limits: RateLimits {
                 requests_per_minute: 120,
                 tokens_per_minute: 1000000, // High limit for local
             },
             enabled: false, // Disabled by default
+            kind: ProviderKind::Builtin,
+            base_url: None,
+            api_key_env: None,
+            headers: HashMap::new(),
+            command: None,
+            args: Vec::new(),
+            fixtures: None,
+        });
+        
+        // Mock provider for offline testing (echoes prompts unless fixtures are configured).
+        // Off until EVALEDS_MOCK_FIXTURES is set, so echoed prompts never land in a real comparison.
+        providers.insert("mock".to_string(), ProviderInfo {
+            available_models: vec![
+                "mock-model".to_string(),
+            ],
+            default_model: "mock-model".to_string(),
+            pricing: HashMap::new(),
+            default_pricing: Some(ModelPricing::free()),
+            tokenizers: HashMap::new(),
+            default_tokenizer: None,
+            rate_limits: RateLimits {
+                requests_per_minute: 100000,
+                tokens_per_minute: 100000000,
+            },
+            enabled: false,
+            kind: ProviderKind::Mock,
+            base_url: None,
+            api_key_env: None,
+            headers: HashMap::new(),
+            command: None,
+            args: Vec::new(),
+            fixtures: None,
         });
         
         Self {
@@ -172,6 +320,9 @@ impl Default for EvalEdsConfig {
                 timeout_seconds: 120,
                 max_concurrent: 5,
                 retry_attempts: 3,
+                response_cache: true,
+                cache_ttl_hours: default_cache_ttl_hours(),
+                runs_kept: default_runs_kept(),
             },
             analysis: AnalysisSettings {
                 enable_similarity_analysis: true,
@@ -180,6 +331,7 @@ impl Default for EvalEdsConfig {
                 similarity_threshold: 0.7,
                 max_keywords: 10,
             },
+            credential_profile: None,
         }
     }
 }
@@ -229,6 +381,7 @@ fn merge_configs(base: EvalEdsConfig, override_config: EvalEdsConfig) -> EvalEds
         },
         defaults: override_config.defaults, // Replace entirely
         analysis: override_config.analysis, // Replace entirely
+        credential_profile: override_config.credential_profile.or(base.credential_profile),
     }
 }
 
@@ -252,6 +405,19 @@ fn apply_env_overrides(mut config: EvalEdsConfig) -> Result<EvalEdsConfig> {
         }
     }
     
+    if let Ok(profile) = std::env::var("EVALEDS_PROFILE") {
+        if !profile.is_empty() {
+            config.credential_profile = Some(profile);
+        }
+    }
+    
+    if let Ok(fixtures) = std::env::var("EVALEDS_MOCK_FIXTURES") {
+        if let Some(mock) = config.providers.get_mut("mock") {
+            mock.fixtures = Some(fixtures);
+            mock.enabled = true;
+        }
+    }
+    
     Ok(config)
 }
 
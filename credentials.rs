@@ -0,0 +1,165 @@
+// Named API key profiles kept outside the environment
+use crate::core::cassette::HttpClient;
+use crate::utils::config::get_user_config_path;
+use crate::utils::error::{Result, EvalError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// API keys grouped into named profiles (`team-prod`, `personal`), stored in
+/// `credentials.toml` next to the user config and readable only by its owner
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CredentialStore {
+    /// Profile name -> provider name -> API key
+    #[serde(default)]
+    pub profiles: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl CredentialStore {
+    pub fn path() -> Result<PathBuf> {
+        Ok(get_user_config_path()?.with_file_name("credentials.toml"))
+    }
+    
+    /// A missing file is treated as an empty store. Like ssh with private keys, a file
+    /// other users can read is refused rather than used.
+    pub async fn load() -> Result<Self> {
+        let path = Self::path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = tokio::fs::metadata(&path).await?.permissions().mode();
+            if mode & 0o077 != 0 {
+                return Err(EvalError::ConfigError(format!(
+                    "credentials file {} is accessible by other users (mode {:o}); run 'chmod 600 {}'",
+                    path.display(), mode & 0o777, path.display()
+                )));
+            }
+        }
+        
+        let contents = tokio::fs::read_to_string(&path).await?;
+        toml::from_str(&contents)
+            .map_err(|e| EvalError::ConfigError(format!("invalid credentials file {}: {}", path.display(), e)))
+    }
+    
+    /// Written with mode 0600, tightening the permissions of an existing file as well
+    pub async fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        
+        let contents = toml::to_string_pretty(self)
+            .map_err(|e| EvalError::SerializationError(e.to_string()))?;
+        
+        let mut options = tokio::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+        
+        use tokio::io::AsyncWriteExt;
+        let mut file = options.open(&path).await?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(std::fs::Permissions::from_mode(0o600)).await?;
+        }
+        file.write_all(contents.as_bytes()).await?;
+        file.flush().await?;
+        
+        Ok(())
+    }
+    
+    pub fn set_key(&mut self, profile: &str, provider: &str, key: String) {
+        self.profiles.entry(profile.to_string())
+            .or_default()
+            .insert(provider.to_string(), key);
+    }
+    
+    /// Remove one provider's key, dropping the profile once it is empty
+    pub fn remove_key(&mut self, profile: &str, provider: &str) -> bool {
+        let removed = match self.profiles.get_mut(profile) {
+            Some(keys) => keys.remove(provider).is_some(),
+            None => false,
+        };
+        if self.profiles.get(profile).is_some_and(|keys| keys.is_empty()) {
+            self.profiles.remove(profile);
+        }
+        removed
+    }
+    
+    pub fn remove_profile(&mut self, profile: &str) -> bool {
+        self.profiles.remove(profile).is_some()
+    }
+}
+
+/// The API keys one run authenticates with
+#[derive(Debug, Clone, Default)]
+pub struct Credentials {
+    profile: Option<String>,
+    keys: BTreeMap<String, String>,
+}
+
+impl Credentials {
+    /// Keys from the named profile, or from environment variables when no profile is selected
+    pub async fn load(profile: Option<&str>) -> Result<Self> {
+        let profile = match profile {
+            Some(profile) => profile,
+            None => return Ok(Self::default()),
+        };
+        
+        let mut store = CredentialStore::load().await?;
+        let keys = store.profiles.remove(profile).ok_or_else(|| EvalError::ConfigError(format!(
+            "credential profile '{}' not found; add a key with 'evaleds credentials add {} <provider>'",
+            profile, profile
+        )))?;
+        
+        Ok(Self { profile: Some(profile.to_string()), keys })
+    }
+    
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+    
+    /// The selected profile's key for this provider, if it has one
+    pub fn profile_key(&self, provider: &str) -> Option<&str> {
+        self.keys.get(provider).map(|key| key.as_str())
+    }
+    
+    /// A selected profile is authoritative: its key wins over an exported environment
+    /// variable, and a provider the profile has no key for is not set up rather than
+    /// falling back to the environment. Replayed runs never reach the network, so a
+    /// placeholder is used when the key is missing.
+    pub fn api_key(&self, provider: &str, var: &str, http: &HttpClient) -> Result<String> {
+        if let Some(profile) = &self.profile {
+            return match self.keys.get(provider) {
+                Some(key) => Ok(key.clone()),
+                None if http.is_replay() => Ok("replay".to_string()),
+                None => Err(EvalError::ConfigError(
+                    format!("no {} key in credential profile '{}'", provider, profile)
+                )),
+            };
+        }
+        
+        match std::env::var(var) {
+            Ok(key) => Ok(key),
+            Err(_) if http.is_replay() => Ok("replay".to_string()),
+            Err(_) => Err(EvalError::ConfigError(
+                format!("{} environment variable not found", var)
+            )),
+        }
+    }
+}
+
+/// Show only enough of a key to tell keys apart
+pub fn mask_key(key: &str) -> String {
+    let chars: Vec<char> = key.chars().collect();
+    if chars.len() <= 8 {
+        return "****".to_string();
+    }
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("{}...{}", chars[..3].iter().collect::<String>(), tail)
+}
\ No newline at end of file
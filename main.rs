@@ -39,6 +39,9 @@ async fn main() {
         cli::args::Commands::Show(args) => {
             cli::commands::show::execute(args).await
         },
+        cli::args::Commands::Runs(args) => {
+            cli::commands::runs::execute(args).await
+        },
         cli::args::Commands::List(args) => {
             cli::commands::list::execute(args).await
         },
@@ -51,6 +54,12 @@ async fn main() {
         cli::args::Commands::Copy(args) => {
             cli::commands::copy::execute(args).await
         },
+        cli::args::Commands::Providers(args) => {
+            cli::commands::providers::execute(args).await
+        },
+        cli::args::Commands::Credentials(args) => {
+            cli::commands::credentials::execute(args).await
+        },
     };
     
     // Handle errors with GNU-style formatting (PromptEds pattern)
@@ -93,9 +102,25 @@ mod core {
         pub use evaleds::core::providers::*;
     }
     
+    pub mod cassette {
+        pub use evaleds::core::cassette::*;
+    }
+    
+    pub mod response_cache {
+        pub use evaleds::core::response_cache::*;
+    }
+    
+    pub mod catalog {
+        pub use evaleds::core::catalog::*;
+    }
+    
     pub mod analysis {
         pub use evaleds::core::analysis::*;
     }
+    
+    pub mod schema {
+        pub use evaleds::core::schema::*;
+    }
 }
 
 // Utilities module
@@ -107,6 +132,10 @@ mod utils {
     pub mod config {
         pub use evaleds::utils::config::*;
     }
+    
+    pub mod credentials {
+        pub use evaleds::utils::credentials::*;
+    }
 }
 
 // UI module (PromptEds style)
@@ -0,0 +1,189 @@
+// Cost estimation from the pricing tables in config
+use crate::utils::config::{EvalEdsConfig, ModelPricing};
+use std::collections::HashMap;
+
+/// Per-provider model prices loaded from `EvalEdsConfig.providers[*].pricing`
+#[derive(Debug, Clone, Default)]
+pub struct PricingTable {
+    providers: HashMap<String, ProviderPricing>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct ProviderPricing {
+    models: HashMap<String, ModelPricing>,
+    default: Option<ModelPricing>,
+}
+
+/// Cost of a single execution
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CostEstimate {
+    pub input_cost: f64,
+    pub output_cost: f64,
+    /// What the same call would cost through the provider's batch API, when a discount is configured
+    pub batch_cost: Option<f64>,
+}
+
+impl CostEstimate {
+    pub fn total(&self) -> f64 {
+        self.input_cost + self.output_cost
+    }
+}
+
+impl PricingTable {
+    pub fn from_config(config: &EvalEdsConfig) -> Self {
+        let providers = config.providers.iter()
+            .map(|(name, info)| (name.clone(), ProviderPricing {
+                models: info.pricing.clone(),
+                default: info.default_pricing.clone(),
+            }))
+            .collect();
+        
+        Self { providers }
+    }
+    
+    /// Exact model match first, then the provider's default pricing. Prefix matching is
+    /// deliberately not attempted: `gpt-4` prices applied to `gpt-4o` would be silently wrong.
+    pub fn lookup(&self, provider: &str, model: &str) -> Option<&ModelPricing> {
+        let pricing = self.providers.get(provider)?;
+        pricing.models.get(model).or(pricing.default.as_ref())
+    }
+    
+    /// Returns `None` when the model has no configured price
+    pub fn estimate(
+        &self,
+        provider: &str,
+        model: &str,
+        input_tokens: u32,
+        cached_input_tokens: u32,
+        cache_write_input_tokens: u32,
+        output_tokens: u32,
+    ) -> Option<CostEstimate> {
+        let pricing = self.lookup(provider, model)?;
+        
+        // Prompt tokens read from or written to the provider's prompt cache are billed at
+        // their own rates when configured
+        let cached_tokens = cached_input_tokens.min(input_tokens);
+        let cache_write_tokens = cache_write_input_tokens.min(input_tokens - cached_tokens);
+        let uncached_tokens = input_tokens - cached_tokens - cache_write_tokens;
+        let cached_price = pricing.cached_input_price_per_1k.unwrap_or(pricing.input_price_per_1k);
+        let cache_write_price = pricing.cache_write_price_per_1k.unwrap_or(pricing.input_price_per_1k);
+        
+        let input_cost = (uncached_tokens as f64 / 1000.0) * pricing.input_price_per_1k
+            + (cached_tokens as f64 / 1000.0) * cached_price
+            + (cache_write_tokens as f64 / 1000.0) * cache_write_price;
+        let output_cost = (output_tokens as f64 / 1000.0) * pricing.output_price_per_1k;
+        
+        let batch_cost = pricing.batch_discount
+            .map(|discount| (input_cost + output_cost) * (1.0 - discount.clamp(0.0, 1.0)));
+        
+        Some(CostEstimate {
+            input_cost,
+            output_cost,
+            batch_cost,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn table() -> PricingTable {
+        let mut openai = ProviderPricing::default();
+        openai.models.insert("gpt-4".to_string(), ModelPricing::new(0.03, 0.06));
+        
+        let mut anthropic = ProviderPricing::default();
+        anthropic.models.insert("claude-3-haiku".to_string(), ModelPricing {
+            input_price_per_1k: 0.00025,
+            output_price_per_1k: 0.00125,
+            cached_input_price_per_1k: Some(0.000025),
+            cache_write_price_per_1k: Some(0.0003125),
+            batch_discount: None,
+        });
+        openai.models.insert("gpt-4o-mini".to_string(), ModelPricing {
+            input_price_per_1k: 0.00015,
+            output_price_per_1k: 0.0006,
+            cached_input_price_per_1k: Some(0.000075),
+            cache_write_price_per_1k: None,
+            batch_discount: Some(0.5),
+        });
+        
+        let local = ProviderPricing {
+            models: HashMap::new(),
+            default: Some(ModelPricing::free()),
+        };
+        
+        let mut providers = HashMap::new();
+        providers.insert("openai".to_string(), openai);
+        providers.insert("anthropic".to_string(), anthropic);
+        providers.insert("local".to_string(), local);
+        PricingTable { providers }
+    }
+    
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-12
+    }
+    
+    #[test]
+    fn lookup_matches_exact_model_names_only() {
+        let table = table();
+        assert_eq!(table.lookup("openai", "gpt-4").unwrap().input_price_per_1k, 0.03);
+        assert!(table.lookup("openai", "gpt-4o").is_none());
+        assert!(table.lookup("openai", "GPT-4").is_none());
+    }
+    
+    #[test]
+    fn lookup_falls_back_to_provider_default() {
+        let table = table();
+        assert_eq!(table.lookup("local", "llama3").unwrap().output_price_per_1k, 0.0);
+    }
+    
+    #[test]
+    fn unknown_provider_or_model_is_unpriced() {
+        let table = table();
+        assert!(table.lookup("anthropic", "claude").is_none());
+        assert!(table.estimate("openai", "gpt-4o", 1000, 0, 0, 1000).is_none());
+        assert!(PricingTable::default().estimate("openai", "gpt-4", 1000, 0, 0, 1000).is_none());
+    }
+    
+    #[test]
+    fn estimate_prices_input_and_output_separately() {
+        let cost = table().estimate("openai", "gpt-4", 2000, 0, 0, 500).unwrap();
+        assert!(close(cost.input_cost, 0.06));
+        assert!(close(cost.output_cost, 0.03));
+        assert!(close(cost.total(), 0.09));
+        assert!(cost.batch_cost.is_none());
+    }
+    
+    #[test]
+    fn cached_tokens_use_the_cached_rate() {
+        let cost = table().estimate("openai", "gpt-4o-mini", 2000, 1000, 0, 0).unwrap();
+        assert!(close(cost.input_cost, 0.00015 + 0.000075));
+        
+        // Without a cached rate, cached tokens cost the same as the rest
+        let cost = table().estimate("openai", "gpt-4", 2000, 1000, 0, 0).unwrap();
+        assert!(close(cost.input_cost, 0.06));
+    }
+    
+    #[test]
+    fn cached_tokens_beyond_input_are_ignored() {
+        let cost = table().estimate("openai", "gpt-4o-mini", 1000, 5000, 0, 0).unwrap();
+        assert!(close(cost.input_cost, 0.000075));
+    }
+    
+    #[test]
+    fn cache_writes_use_the_cache_write_rate() {
+        let cost = table().estimate("anthropic", "claude-3-haiku", 3000, 1000, 1000, 0).unwrap();
+        assert!(close(cost.input_cost, 0.00025 + 0.000025 + 0.0003125));
+        
+        // Without a cache write rate, written tokens cost the same as the rest
+        let cost = table().estimate("openai", "gpt-4", 2000, 0, 1000, 0).unwrap();
+        assert!(close(cost.input_cost, 0.06));
+    }
+    
+    #[test]
+    fn batch_discount_applies_to_the_total() {
+        let cost = table().estimate("openai", "gpt-4o-mini", 1000, 0, 0, 1000).unwrap();
+        assert!(close(cost.batch_cost.unwrap(), (0.00015 + 0.0006) * 0.5));
+    }
+}
\ No newline at end of file
@@ -1,17 +1,53 @@
 // Provider Manager and individual provider implementations
-use crate::core::evaluation::{ModelSettings, ExecutionResult, ExecutionMetadata, ExecutionStatus};
-use crate::utils::error::Result;
+use crate::core::evaluation::{ModelSettings, ExecutionResult, ExecutionMetadata, ExecutionStatus, RateLimitInfo, TokenCountSource, ChatMessage, MessageRole, ToolDefinition, ToolCall, ImageAttachment, ImageData, render_transcript, prompt_hash, prompt_id, cell_id};
+use crate::core::streaming::{LineReader, StreamTimer, StreamTiming, sse_data};
+use crate::core::cassette::{HttpClient, HttpResponse};
+use crate::core::rate_limiter::RateLimiter;
+use crate::core::response_cache::{ResponseCache, CachedResponse};
+use crate::utils::config::{load_config, EvalEdsConfig, ProviderInfo, ProviderKind};
+use crate::utils::credentials::Credentials;
+use crate::core::pricing::PricingTable;
+use crate::core::tokenizer::{Tokenizer, TokenizerRegistry};
+use crate::utils::error::{Result, EvalError, ApiError, ApiErrorKind};
 use async_trait::async_trait;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use std::collections::HashMap;
+use std::sync::Arc;
 use serde::{Deserialize, Serialize};
 
 #[async_trait]
 pub trait Provider: Send + Sync {
-    async fn execute(&self, prompt: &str, model: &str, settings: &ModelSettings) -> Result<ProviderResponse>;
+    async fn execute(&self, messages: &[ChatMessage], model: &str, settings: &ModelSettings) -> Result<ProviderResponse>;
     fn get_models(&self) -> Vec<String>;
-    fn estimate_cost(&self, model: &str, input_tokens: u32, output_tokens: u32) -> f64;
     fn supports_streaming(&self) -> bool { false }
+    
+    /// `ModelSettings` fields this provider's API has no equivalent for.
+    /// Requests are still sent; the run warns that the values are ignored.
+    fn ignored_settings(&self) -> &'static [&'static str] { &[] }
+    
+    /// Models reported by the provider's model-listing endpoint.
+    /// `None` when the provider has no such endpoint.
+    async fn list_models(&self) -> Result<Option<Vec<String>>> {
+        Ok(None)
+    }
+    
+    /// Execute with a streamed response, recording token timing.
+    /// Providers without streaming support fall back to a buffered request.
+    async fn execute_streaming(&self, messages: &[ChatMessage], model: &str, settings: &ModelSettings) -> Result<ProviderResponse> {
+        self.execute(messages, model, settings).await
+    }
+    
+    /// Execute with tools the model may call. Providers without tool support reject the
+    /// request rather than letting the model answer in free text.
+    async fn execute_with_tools(&self, _messages: &[ChatMessage], _tools: &[ToolDefinition], _model: &str, _settings: &ModelSettings) -> Result<ProviderResponse> {
+        Err(EvalError::ProviderError("tool calling is not supported by this provider".to_string()))
+    }
+    
+    /// Execute asking for JSON output matching `schema`. Providers without a structured
+    /// output mode send the plain request; the output is still checked against the schema.
+    async fn execute_structured(&self, messages: &[ChatMessage], _schema: &serde_json::Value, model: &str, settings: &ModelSettings) -> Result<ProviderResponse> {
+        self.execute(messages, model, settings).await
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -19,6 +55,8 @@ pub struct ProviderResponse {
     pub content: String,
     pub usage: Usage,
     pub metadata: HashMap<String, serde_json::Value>,
+    pub timing: Option<StreamTiming>,
+    pub tool_calls: Vec<ToolCall>,
 }
 
 #[derive(Debug, Clone)]
@@ -26,99 +64,567 @@ pub struct Usage {
     pub input_tokens: u32,
     pub output_tokens: u32,
     pub total_tokens: u32,
+    pub cached_input_tokens: u32,             // Part of input_tokens served from the provider's prompt cache
+    pub cache_write_input_tokens: u32,        // Part of input_tokens written to the provider's prompt cache
+    pub input_source: TokenCountSource,
+    pub output_source: TokenCountSource,
+}
+
+impl Usage {
+    /// Counts reported by the provider
+    pub fn reported(input_tokens: u32, output_tokens: u32, cached_input_tokens: u32) -> Self {
+        Self {
+            input_tokens,
+            output_tokens,
+            total_tokens: input_tokens + output_tokens,
+            cached_input_tokens,
+            cache_write_input_tokens: 0,
+            input_source: TokenCountSource::Provider,
+            output_source: TokenCountSource::Provider,
+        }
+    }
+    
+    pub fn with_cache_write_tokens(mut self, cache_write_input_tokens: u32) -> Self {
+        self.cache_write_input_tokens = cache_write_input_tokens;
+        self
+    }
+    
+    /// Use whichever counts the provider reported and estimate the rest from text length.
+    /// Estimates are replaced by the model's tokenizer when one is configured.
+    pub fn from_counts(
+        input_tokens: Option<u32>,
+        output_tokens: Option<u32>,
+        cached_input_tokens: u32,
+        messages: &[ChatMessage],
+        content: &str,
+    ) -> Self {
+        let (input_tokens, input_source) = match input_tokens {
+            Some(tokens) => (tokens, TokenCountSource::Provider),
+            None => {
+                let prompt_chars: usize = messages.iter().map(|m| m.content.len()).sum();
+                ((prompt_chars / 4) as u32, TokenCountSource::Estimate)
+            }
+        };
+        let (output_tokens, output_source) = match output_tokens {
+            Some(tokens) => (tokens, TokenCountSource::Provider),
+            None => (estimate_tokens(content), TokenCountSource::Estimate),
+        };
+        
+        Self {
+            input_tokens,
+            output_tokens,
+            total_tokens: input_tokens + output_tokens,
+            cached_input_tokens,
+            cache_write_input_tokens: 0,
+            input_source,
+            output_source,
+        }
+    }
 }
 
 pub struct ProviderManager {
     providers: HashMap<String, Box<dyn Provider>>,
+    skipped: HashMap<String, String>,         // Providers that were not initialized, with the reason
+    credential_profile: Option<String>,       // Profile the API keys came from; None for environment variables
+    streaming: bool,
+    http: HttpClient,
+    rate_limiters: HashMap<String, RateLimiter>,
+    throttle_notifier: Option<ThrottleNotifier>,
+    retry_attempts: u32,
+    timeout: Option<Duration>,
+    pricing: PricingTable,
+    tokenizers: TokenizerRegistry,
+    cache: Option<ResponseCache>,
 }
 
+/// Providers shipped with EvalEds, set up without a config entry
+pub const BUILTIN_PROVIDERS: [&str; 4] = ["openai", "anthropic", "google", "local"];
+
+/// Called with the provider name and wait time whenever a request is held back by rate limits
+pub type ThrottleNotifier = Arc<dyn Fn(&str, Duration) + Send + Sync>;
+
 impl ProviderManager {
     pub async fn new() -> Result<Self> {
+        Self::with_http(HttpClient::live()).await
+    }
+    
+    /// Build providers on top of the given HTTP client (live, recording or replaying)
+    pub async fn with_http(http: HttpClient) -> Result<Self> {
+        Self::with_profile(http, None).await
+    }
+    
+    /// Build providers authenticating with the given credential profile. Without one,
+    /// the profile selected in config is used, and environment variables failing that.
+    pub async fn with_profile(http: HttpClient, profile: Option<&str>) -> Result<Self> {
+        let config = load_config().await?;
+        let credentials = Credentials::load(profile.or(config.credential_profile.as_deref())).await?;
+        Self::from_config(http, config, credentials).await
+    }
+    
+    async fn from_config(http: HttpClient, config: EvalEdsConfig, credentials: Credentials) -> Result<Self> {
         let mut providers: HashMap<String, Box<dyn Provider>> = HashMap::new();
+        let mut skipped: HashMap<String, String> = HashMap::new();
         
-        // Initialize providers
-        if let Ok(openai_provider) = OpenAIProvider::new().await {
-            providers.insert("openai".to_string(), Box::new(openai_provider));
-        }
-        
-        if let Ok(anthropic_provider) = AnthropicProvider::new().await {
-            providers.insert("anthropic".to_string(), Box::new(anthropic_provider));
+        // Built-in providers run unless disabled in config; a missing API key skips them
+        for name in BUILTIN_PROVIDERS {
+            if config.providers.get(name).is_some_and(|info| !info.enabled) {
+                skipped.insert(name.to_string(), "disabled in config".to_string());
+                continue;
+            }
+            
+            let provider: Result<Box<dyn Provider>> = match name {
+                "openai" => OpenAIProvider::new(http.clone(), &credentials).await.map(|p| Box::new(p) as Box<dyn Provider>),
+                "anthropic" => AnthropicProvider::new(http.clone(), &credentials).await.map(|p| Box::new(p) as Box<dyn Provider>),
+                "google" => GoogleProvider::new(http.clone(), &credentials).await.map(|p| Box::new(p) as Box<dyn Provider>),
+                _ => LocalProvider::new(http.clone()).await.map(|p| Box::new(p) as Box<dyn Provider>),
+            };
+            match provider {
+                Ok(provider) => {
+                    providers.insert(name.to_string(), provider);
+                }
+                Err(e) => {
+                    skipped.insert(name.to_string(), e.to_string());
+                }
+            }
         }
         
-        if let Ok(google_provider) = GoogleProvider::new().await {
-            providers.insert("google".to_string(), Box::new(google_provider));
+        // Additional providers declared in config
+        for (name, info) in &config.providers {
+            if !info.enabled {
+                skipped.insert(name.clone(), "disabled in config".to_string());
+                continue;
+            }
+            
+            match info.kind {
+                // Entries for built-in providers only tune the ones set up above; any other
+                // name (a typo, or a forgotten `kind`) would otherwise be silently ignored
+                ProviderKind::Builtin => {
+                    if !BUILTIN_PROVIDERS.contains(&name.as_str()) {
+                        skipped.insert(name.clone(), "not a built-in provider; set kind".to_string());
+                    }
+                }
+                ProviderKind::OpenaiCompatible => {
+                    match OpenAIProvider::compatible(name, info, http.clone(), &credentials) {
+                        Ok(provider) => {
+                            providers.insert(name.clone(), Box::new(provider));
+                        }
+                        Err(e) => {
+                            skipped.insert(name.clone(), e.to_string());
+                        }
+                    }
+                }
+                ProviderKind::Process => {
+                    match ProcessProvider::new(name, info) {
+                        Ok(provider) => {
+                            providers.insert(name.clone(), Box::new(provider));
+                        }
+                        Err(e) => {
+                            skipped.insert(name.clone(), e.to_string());
+                        }
+                    }
+                }
+                ProviderKind::Mock => {
+                    // A broken fixture file skips the provider; a run using it then fails
+                    // with the reason instead of silently echoing
+                    match MockProvider::new(name, info).await {
+                        Ok(provider) => {
+                            providers.insert(name.clone(), Box::new(provider));
+                        }
+                        Err(e) => {
+                            skipped.insert(name.clone(), e.to_string());
+                        }
+                    }
+                }
+            }
         }
         
-        if let Ok(local_provider) = LocalProvider::new().await {
-            providers.insert("local".to_string(), Box::new(local_provider));
-        }
+        let rate_limiters = config.providers.iter()
+            .map(|(name, info)| (name.clone(), RateLimiter::new(&info.rate_limits)))
+            .collect();
         
-        Ok(Self { providers })
+        Ok(Self {
+            providers,
+            skipped,
+            credential_profile: credentials.profile().map(str::to_string),
+            streaming: false,
+            http,
+            rate_limiters,
+            throttle_notifier: None,
+            retry_attempts: config.defaults.retry_attempts,
+            timeout: timeout_from_secs(config.defaults.timeout_seconds),
+            pricing: PricingTable::from_config(&config),
+            tokenizers: TokenizerRegistry::from_config(&config),
+            cache: None,
+        })
+    }
+    
+    /// Stream responses from providers that support it to capture
+    /// time-to-first-token and throughput metrics
+    pub fn with_streaming(mut self, streaming: bool) -> Self {
+        self.streaming = streaming;
+        self
+    }
+    
+    /// Serve repeated requests from the response cache and store new successful responses
+    pub fn with_cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+    
+    pub fn with_throttle_notifier(mut self, notifier: ThrottleNotifier) -> Self {
+        self.throttle_notifier = Some(notifier);
+        self
+    }
+    
+    /// Retries for rate limits, server errors and network failures (0 disables retrying)
+    pub fn with_retry_attempts(mut self, retry_attempts: u32) -> Self {
+        self.retry_attempts = retry_attempts;
+        self
+    }
+    
+    /// Upper bound on each provider call; a zero duration disables the timeout
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout_from_secs(timeout.as_secs());
+        self
     }
     
     pub async fn execute_prompt(
         &self,
         provider_name: &str,
         model: &str,
-        prompt: &str,
+        messages: &[ChatMessage],
+        tools: &[ToolDefinition],
+        response_schema: Option<&serde_json::Value>,
         settings: &ModelSettings,
+        repetition: u32,
+        repetitions: u32,
     ) -> Result<ExecutionResult> {
-        let provider = self.providers.get(provider_name)
-            .ok_or_else(|| crate::utils::error::EvalError::ProviderError(
-                format!("Provider '{}' not available", provider_name)
-            ))?;
+        let provider = self.provider(provider_name)?;
         
-        let start_time = Instant::now();
+        let input = render_transcript(messages);
+        let images: Vec<ImageAttachment> = messages.iter()
+            .flat_map(|m| &m.images)
+            .map(ImageData::attachment)
+            .collect();
+        
+        // Cells sampled more than once always call the provider, so every sample of a re-run
+        // is measured in that run and the variance never mixes in an older response
+        let cache = self.cache.as_ref().filter(|_| repetitions <= 1);
+        let cache_key = cache.map(|_| {
+            ResponseCache::key(provider_name, model, messages, tools, response_schema, settings)
+        });
+        if let (Some(cache), Some(key)) = (cache, &cache_key) {
+            if let Some(entry) = cache.get(key).await {
+                return Ok(self.cached_result(provider_name, model, messages, input, images, entry, repetition));
+            }
+        }
+        
+        // Output is budgeted at max_tokens until the real usage is known
+        let estimated_tokens = self.prompt_tokens(provider_name, model, messages)
+            + settings.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS);
+        // Replayed responses never reach the provider, so neither its limits nor backoff apply
+        let replaying = self.http.is_replay();
+        let limiter = if replaying { None } else { self.rate_limiters.get(provider_name) };
+        
+        let mut throttle_wait = Duration::ZERO;
+        let mut retry_wait = Duration::ZERO;
+        let mut retry_errors = Vec::new();
+        let mut timed_out = false;
         
-        match provider.execute(prompt, model, settings).await {
+        let (outcome, response_time) = loop {
+            // Hold the request until the provider's request and token budgets allow it
+            if let Some(limiter) = limiter {
+                throttle_wait += limiter.acquire(estimated_tokens, |wait| {
+                    if let Some(notify) = &self.throttle_notifier {
+                        notify(provider_name, wait);
+                    }
+                }).await;
+            }
+            
+            let start_time = Instant::now();
+            let call = async {
+                // Tool calls and structured output are checked as a whole, so neither is streamed
+                if !tools.is_empty() {
+                    provider.execute_with_tools(messages, tools, model, settings).await
+                } else if let Some(schema) = response_schema {
+                    provider.execute_structured(messages, schema, model, settings).await
+                } else if self.streaming && provider.supports_streaming() {
+                    provider.execute_streaming(messages, model, settings).await
+                } else {
+                    provider.execute(messages, model, settings).await
+                }
+            };
+            
+            // Dropping the call on timeout also cancels the in-flight request
+            let outcome = match self.timeout {
+                Some(timeout) => match tokio::time::timeout(timeout, call).await {
+                    Ok(outcome) => outcome,
+                    Err(_) => {
+                        timed_out = true;
+                        Err(EvalError::ProviderError(format!("Request timed out after {}s", timeout.as_secs())))
+                    }
+                },
+                None => call.await,
+            };
+            let response_time = start_time.elapsed();
+            let outcome = outcome.map(|response| self.count_missing_tokens(provider_name, model, messages, response));
+            
+            if let Some(limiter) = limiter {
+                let used = outcome.as_ref().map(|r| r.usage.total_tokens).unwrap_or(0);
+                limiter.settle(estimated_tokens, used).await;
+            }
+            
+            let retry = match &outcome {
+                Err(e) if is_retryable(e) && (retry_errors.len() as u32) < self.retry_attempts => {
+                    Some((e.to_string(), retry_after(e)))
+                },
+                _ => None,
+            };
+            
+            match retry {
+                Some((error, retry_after)) => {
+                    retry_errors.push(error);
+                    if !replaying {
+                        let delay = backoff_delay(retry_errors.len() as u32, retry_after);
+                        retry_wait += delay;
+                        tokio::time::sleep(delay).await;
+                    }
+                },
+                None => break (outcome, response_time),
+            }
+        };
+        
+        let rate_limit_info = if throttle_wait.is_zero() && retry_errors.is_empty() {
+            None
+        } else {
+            Some(RateLimitInfo {
+                throttle_wait_ms: throttle_wait.as_millis() as u64,
+                retry_attempts: retry_errors.len() as u32,
+                retry_wait_ms: retry_wait.as_millis() as u64,
+                retry_errors,
+            })
+        };
+        
+        match outcome {
             Ok(response) => {
-                let response_time = start_time.elapsed();
-                let cost = provider.estimate_cost(model, response.usage.input_tokens, response.usage.output_tokens);
+                // Unpriced models are flagged rather than given a guessed price
+                let cost = self.pricing.estimate(
+                    provider_name,
+                    model,
+                    response.usage.input_tokens,
+                    response.usage.cached_input_tokens,
+                    response.usage.cache_write_input_tokens,
+                    response.usage.output_tokens,
+                );
+                let response_time_ms = response_time.as_millis() as u64;
                 
-                Ok(ExecutionResult {
+                let (time_to_first_token_ms, inter_token_latency_ms, tokens_per_second) = match &response.timing {
+                    Some(timing) => (
+                        Some(timing.time_to_first_token_ms),
+                        timing.avg_inter_token_latency_ms(),
+                        timing.tokens_per_second(response.usage.output_tokens, response_time_ms),
+                    ),
+                    None => (None, None, None),
+                };
+                
+                let result = ExecutionResult {
                     id: uuid::Uuid::new_v4().to_string(),
-                    prompt_id: uuid::Uuid::new_v4().to_string(),
+                    prompt_id: prompt_id(messages),
                     provider: provider_name.to_string(),
                     model: model.to_string(),
-                    input: prompt.to_string(),
+                    input: input.clone(),
                     output: response.content,
                     metadata: ExecutionMetadata {
-                        response_time_ms: response_time.as_millis() as u64,
+                        response_time_ms,
                         token_count_input: response.usage.input_tokens,
                         token_count_output: response.usage.output_tokens,
-                        cost_usd: cost,
+                        cost_usd: cost.map(|c| c.total()).unwrap_or(0.0),
+                        input_cost_usd: cost.map(|c| c.input_cost).unwrap_or(0.0),
+                        output_cost_usd: cost.map(|c| c.output_cost).unwrap_or(0.0),
+                        batch_cost_usd: cost.and_then(|c| c.batch_cost),
+                        cost_unknown: cost.is_none(),
+                        cached_input_tokens: response.usage.cached_input_tokens,
+                        cache_write_input_tokens: response.usage.cache_write_input_tokens,
+                        token_count_source: response.usage.input_source.max(response.usage.output_source),
                         timestamp: chrono::Utc::now(),
                         error: None,
-                        rate_limit_info: None,
+                        rate_limit_info,
+                        time_to_first_token_ms,
+                        inter_token_latency_ms,
+                        tokens_per_second,
+                        provider_metadata: response.metadata,
+                        cache_hit: false,
                     },
                     status: ExecutionStatus::Success,
-                })
+                    tool_calls: response.tool_calls,
+                    expected_tool_calls: Vec::new(),
+                    response_schema: None,
+                    images,
+                    cell_id: cell_id(provider_name, model, None, messages),
+                    repetition,
+                    resolved_prompt: None,
+                };
+                
+                if let (Some(cache), Some(key)) = (cache, &cache_key) {
+                    let entry = CachedResponse {
+                        provider: result.provider.clone(),
+                        model: result.model.clone(),
+                        stored_at: chrono::Utc::now(),
+                        output: result.output.clone(),
+                        tool_calls: result.tool_calls.clone(),
+                        metadata: result.metadata.clone(),
+                    };
+                    // A cache that can't be written only costs the saving on the next run
+                    if let Err(e) = cache.put(key, &entry).await {
+                        log::warn!("could not store response in cache: {}", e);
+                    }
+                }
+                
+                Ok(result)
             },
             Err(e) => {
-                let response_time = start_time.elapsed();
-                
                 Ok(ExecutionResult {
                     id: uuid::Uuid::new_v4().to_string(),
-                    prompt_id: uuid::Uuid::new_v4().to_string(),
+                    prompt_id: prompt_id(messages),
                     provider: provider_name.to_string(),
                     model: model.to_string(),
-                    input: prompt.to_string(),
+                    input,
                     output: String::new(),
                     metadata: ExecutionMetadata {
                         response_time_ms: response_time.as_millis() as u64,
                         token_count_input: 0,
                         token_count_output: 0,
                         cost_usd: 0.0,
+                        input_cost_usd: 0.0,
+                        output_cost_usd: 0.0,
+                        batch_cost_usd: None,
+                        cost_unknown: false,
+                        cached_input_tokens: 0,
+                        cache_write_input_tokens: 0,
+                        token_count_source: TokenCountSource::default(),
                         timestamp: chrono::Utc::now(),
                         error: Some(e.to_string()),
-                        rate_limit_info: None,
+                        rate_limit_info,
+                        time_to_first_token_ms: None,
+                        inter_token_latency_ms: None,
+                        tokens_per_second: None,
+                        provider_metadata: HashMap::new(),
+                        cache_hit: false,
                     },
-                    status: ExecutionStatus::Failed,
+                    status: if timed_out { ExecutionStatus::Timeout } else { ExecutionStatus::Failed },
+                    tool_calls: Vec::new(),
+                    expected_tool_calls: Vec::new(),
+                    response_schema: None,
+                    images,
+                    cell_id: cell_id(provider_name, model, None, messages),
+                    repetition,
+                    resolved_prompt: None,
                 })
             }
         }
     }
     
+    /// Result for a cache hit. The cost is recomputed at current prices and reported as
+    /// saved; latency metrics are the ones measured when the response was first fetched.
+    fn cached_result(
+        &self,
+        provider_name: &str,
+        model: &str,
+        messages: &[ChatMessage],
+        input: String,
+        images: Vec<ImageAttachment>,
+        entry: CachedResponse,
+        repetition: u32,
+    ) -> ExecutionResult {
+        let mut metadata = entry.metadata;
+        let cost = self.pricing.estimate(
+            provider_name,
+            model,
+            metadata.token_count_input,
+            metadata.cached_input_tokens,
+            metadata.cache_write_input_tokens,
+            metadata.token_count_output,
+        );
+        metadata.cost_usd = cost.map(|c| c.total()).unwrap_or(0.0);
+        metadata.input_cost_usd = cost.map(|c| c.input_cost).unwrap_or(0.0);
+        metadata.output_cost_usd = cost.map(|c| c.output_cost).unwrap_or(0.0);
+        metadata.batch_cost_usd = cost.and_then(|c| c.batch_cost);
+        metadata.cost_unknown = cost.is_none();
+        metadata.timestamp = chrono::Utc::now();
+        metadata.rate_limit_info = None;
+        metadata.cache_hit = true;
+        
+        ExecutionResult {
+            id: uuid::Uuid::new_v4().to_string(),
+            prompt_id: prompt_id(messages),
+            provider: provider_name.to_string(),
+            model: model.to_string(),
+            input,
+            output: entry.output,
+            metadata,
+            status: ExecutionStatus::Success,
+            tool_calls: entry.tool_calls,
+            expected_tool_calls: Vec::new(),
+            response_schema: None,
+            images,
+            cell_id: cell_id(provider_name, model, None, messages),
+            repetition,
+            resolved_prompt: None,
+        }
+    }
+    
+    /// Prompt size with the model's configured tokenizer, or estimated from text length
+    fn prompt_tokens(&self, provider_name: &str, model: &str, messages: &[ChatMessage]) -> u32 {
+        match self.tokenizers.lookup(provider_name, model) {
+            Some(tokenizer) => count_message_tokens(tokenizer.as_ref(), messages),
+            None => estimate_tokens(&render_transcript(messages)),
+        }
+    }
+    
+    /// Upper bound on what one execution costs before it is sent: the prompt tokens plus
+    /// a full `max_tokens` of output. `None` when the model has no configured price.
+    pub fn estimate_cost(&self, provider_name: &str, model: &str, messages: &[ChatMessage], settings: &ModelSettings) -> Option<f64> {
+        let input_tokens = self.prompt_tokens(provider_name, model, messages);
+        let output_tokens = settings.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS);
+        self.pricing.estimate(provider_name, model, input_tokens, 0, 0, output_tokens)
+            .map(|cost| cost.total())
+    }
+    
+    /// Replace estimated token counts with the model's configured tokenizer, when there is one
+    fn count_missing_tokens(
+        &self,
+        provider_name: &str,
+        model: &str,
+        messages: &[ChatMessage],
+        mut response: ProviderResponse,
+    ) -> ProviderResponse {
+        let tokenizer = match self.tokenizers.lookup(provider_name, model) {
+            Some(tokenizer) => tokenizer,
+            None => return response,
+        };
+        
+        let usage = &mut response.usage;
+        if usage.input_source == TokenCountSource::Estimate {
+            usage.input_tokens = count_message_tokens(tokenizer.as_ref(), messages);
+            usage.input_source = TokenCountSource::Tokenizer;
+        }
+        if usage.output_source == TokenCountSource::Estimate {
+            usage.output_tokens = tokenizer.count_tokens(&response.content);
+            usage.output_source = TokenCountSource::Tokenizer;
+        }
+        usage.total_tokens = usage.input_tokens + usage.output_tokens;
+        
+        response
+    }
+    
+    pub fn pricing(&self) -> &PricingTable {
+        &self.pricing
+    }
+    
+    pub fn http(&self) -> &HttpClient {
+        &self.http
+    }
+    
     pub fn get_available_providers(&self) -> Vec<String> {
         self.providers.keys().cloned().collect()
     }
@@ -126,154 +632,613 @@ impl ProviderManager {
     pub fn get_provider_models(&self, provider_name: &str) -> Option<Vec<String>> {
         self.providers.get(provider_name).map(|p| p.get_models())
     }
+    
+    /// Configured settings the provider will not send
+    pub fn ignored_settings(&self, provider_name: &str, settings: &ModelSettings) -> Vec<&'static str> {
+        let ignored = match self.providers.get(provider_name) {
+            Some(provider) => provider.ignored_settings(),
+            None => return Vec::new(),
+        };
+        
+        settings.configured_fields().into_iter()
+            .filter(|field| ignored.contains(field))
+            .collect()
+    }
+    
+    /// Query the provider's model-listing endpoint
+    pub async fn list_provider_models(&self, provider_name: &str) -> Result<Option<Vec<String>>> {
+        self.provider(provider_name)?.list_models().await
+    }
+    
+    /// Credential profile the providers authenticate with, if one was selected
+    pub fn credential_profile(&self) -> Option<&str> {
+        self.credential_profile.as_deref()
+    }
+    
+    /// Why a provider was not initialized, if it was configured or built in
+    pub fn skip_reason(&self, provider_name: &str) -> Option<&str> {
+        self.skipped.get(provider_name).map(|reason| reason.as_str())
+    }
+    
+    /// Send a minimal request straight to the provider, bypassing rate limits and retries,
+    /// and return how long it took
+    pub async fn probe(&self, provider_name: &str, model: &str) -> Result<Duration> {
+        let provider = self.provider(provider_name)?;
+        let messages = [ChatMessage::user("Reply with the word OK.")];
+        let settings = ModelSettings {
+            max_tokens: Some(5),
+            ..ModelSettings::default()
+        };
+        
+        let start_time = Instant::now();
+        let call = provider.execute(&messages, model, &settings);
+        match self.timeout {
+            Some(timeout) => tokio::time::timeout(timeout, call).await
+                .map_err(|_| EvalError::ProviderError(format!("Request timed out after {}s", timeout.as_secs())))??,
+            None => call.await?,
+        };
+        
+        Ok(start_time.elapsed())
+    }
+    
+    fn provider(&self, provider_name: &str) -> Result<&dyn Provider> {
+        match self.providers.get(provider_name) {
+            Some(provider) => Ok(provider.as_ref()),
+            None => Err(EvalError::ProviderError(match self.skipped.get(provider_name) {
+                Some(reason) => format!("Provider '{}' not available: {}", provider_name, reason),
+                None => format!("Provider '{}' not available: not configured", provider_name),
+            })),
+        }
+    }
+}
+
+/// Sampling defaults applied when a setting is left unset, the same for every provider
+/// so that comparisons don't silently mix vendor defaults
+const DEFAULT_TEMPERATURE: f32 = 0.7;
+const DEFAULT_MAX_TOKENS: u32 = 1000;
+
+/// Set `key` only when the value is configured, leaving the provider's default otherwise
+fn set_optional<T: Serialize>(target: &mut serde_json::Value, key: &str, value: Option<T>) {
+    if let Some(value) = value {
+        target[key] = serde_json::json!(value);
+    }
+}
+
+/// Merge the passthrough settings into a request body. Nested objects are merged key by
+/// key, so `{"generationConfig": {"candidateCount": 2}}` keeps the other generation settings.
+fn merge_extra(request_body: &mut serde_json::Value, extra: &HashMap<String, serde_json::Value>) {
+    for (key, value) in extra {
+        merge_json(&mut request_body[key.as_str()], value);
+    }
+}
+
+fn merge_json(target: &mut serde_json::Value, value: &serde_json::Value) {
+    match (target.as_object_mut(), value.as_object()) {
+        (Some(target), Some(fields)) => {
+            for (key, value) in fields {
+                merge_json(target.entry(key.clone()).or_insert(serde_json::Value::Null), value);
+            }
+        },
+        _ => *target = value.clone(),
+    }
+}
+
+/// Join all system messages into a single system prompt and return the remaining turns.
+/// Used by providers that take the system prompt outside the message list.
+fn split_system_prompt(messages: &[ChatMessage]) -> (Option<String>, Vec<&ChatMessage>) {
+    let system_parts: Vec<&str> = messages.iter()
+        .filter(|m| m.role == MessageRole::System)
+        .map(|m| m.content.as_str())
+        .collect();
+    
+    let turns = messages.iter()
+        .filter(|m| m.role != MessageRole::System)
+        .collect();
+    
+    let system = if system_parts.is_empty() {
+        None
+    } else {
+        Some(system_parts.join("\n\n"))
+    };
+    
+    (system, turns)
+}
+
+fn timeout_from_secs(seconds: u64) -> Option<Duration> {
+    if seconds == 0 {
+        None
+    } else {
+        Some(Duration::from_secs(seconds))
+    }
+}
+
+/// Transient failures: rate limits, 5xx responses and dropped connections
+fn is_retryable(error: &EvalError) -> bool {
+    match error {
+        EvalError::ApiError(api_error) => api_error.is_retryable(),
+        EvalError::NetworkError(_) => true,
+        _ => false,
+    }
+}
+
+fn retry_after(error: &EvalError) -> Option<Duration> {
+    match error {
+        EvalError::ApiError(api_error) => api_error.retry_after,
+        _ => None,
+    }
+}
+
+/// Exponential backoff (1s, 2s, 4s, ... capped at 60s) with jitter in the upper half
+/// of the window. A server-provided Retry-After takes precedence.
+fn backoff_delay(attempt: u32, retry_after: Option<Duration>) -> Duration {
+    if let Some(retry_after) = retry_after {
+        return retry_after;
+    }
+    
+    let base_ms = 1000u64.saturating_mul(1 << attempt.saturating_sub(1).min(6)).min(60_000);
+    let jitter_ms = rand::random::<u64>() % (base_ms / 2 + 1);
+    Duration::from_millis(base_ms / 2 + jitter_ms)
+}
+
+/// Rough token estimate used when neither the provider nor a tokenizer can count
+fn estimate_tokens(text: &str) -> u32 {
+    (text.len() / 4) as u32
+}
+
+/// Message contents only; per-message framing tokens vary by provider and are not counted
+fn count_message_tokens(tokenizer: &dyn Tokenizer, messages: &[ChatMessage]) -> u32 {
+    messages.iter().map(|m| tokenizer.count_tokens(&m.content)).sum()
+}
+
+/// Turn non-2xx responses into classified API errors before the body is parsed
+async fn check_status(response: HttpResponse) -> Result<HttpResponse> {
+    if response.is_success() {
+        return Ok(response);
+    }
+    
+    let status = response.status();
+    let retry_after = response.retry_after();
+    let body = response.text().await.unwrap_or_default();
+    Err(EvalError::ApiError(ApiError::from_response(status, &body, retry_after)))
+}
+
+fn empty_response(what: &str) -> EvalError {
+    EvalError::ApiError(ApiError::new(ApiErrorKind::EmptyResponse, format!("response contained no {}", what)))
+}
+
+fn content_filtered(reason: &str) -> EvalError {
+    EvalError::ApiError(ApiError::new(ApiErrorKind::ContentFilter, reason.to_string()))
+}
+
+/// Chat message in the OpenAI format. With images the content becomes a list of parts.
+fn openai_message(message: &ChatMessage) -> serde_json::Value {
+    if message.images.is_empty() {
+        return serde_json::json!({"role": message.role.as_str(), "content": message.content});
+    }
+    
+    let mut parts = vec![serde_json::json!({"type": "text", "text": message.content})];
+    parts.extend(message.images.iter().map(|image| serde_json::json!({
+        "type": "image_url",
+        "image_url": {"url": image.data_url()},
+    })));
+    serde_json::json!({"role": message.role.as_str(), "content": parts})
+}
+
+/// OpenAI `tools` format, also accepted by Ollama and most OpenAI-compatible servers
+fn openai_tools(tools: &[ToolDefinition]) -> serde_json::Value {
+    tools.iter()
+        .map(|tool| serde_json::json!({
+            "type": "function",
+            "function": {
+                "name": tool.name,
+                "description": tool.description.clone().unwrap_or_default(),
+                "parameters": tool.parameters,
+            }
+        }))
+        .collect()
+}
+
+/// `message.tool_calls` from an OpenAI or Ollama response. OpenAI sends arguments as a
+/// JSON string and Ollama as an object; strings that fail to parse are kept as-is.
+fn openai_tool_calls(message: &serde_json::Value) -> Vec<ToolCall> {
+    let calls = match message["tool_calls"].as_array() {
+        Some(calls) => calls,
+        None => return Vec::new(),
+    };
+    
+    calls.iter()
+        .filter_map(|call| {
+            let function = &call["function"];
+            let arguments = match &function["arguments"] {
+                serde_json::Value::String(raw) => serde_json::from_str(raw)
+                    .unwrap_or_else(|_| serde_json::Value::String(raw.clone())),
+                other => other.clone(),
+            };
+            
+            Some(ToolCall {
+                id: call["id"].as_str().map(|id| id.to_string()),
+                name: function["name"].as_str()?.to_string(),
+                arguments,
+            })
+        })
+        .collect()
 }
 
 // OpenAI Provider Implementation
+// Also serves any openai-compatible server declared in config.
 pub struct OpenAIProvider {
-    client: reqwest::Client,
-    api_key: String,
+    client: HttpClient,
+    api_key: Option<String>,
+    base_url: String,
+    headers: HashMap<String, String>,
+    models: Vec<String>,
 }
 
 impl OpenAIProvider {
-    pub async fn new() -> Result<Self> {
-        let api_key = std::env::var("OPENAI_API_KEY")
-            .map_err(|_| crate::utils::error::EvalError::ConfigError(
-                "OPENAI_API_KEY environment variable not found".to_string()
+    pub async fn new(client: HttpClient, credentials: &Credentials) -> Result<Self> {
+        let api_key = credentials.api_key("openai", "OPENAI_API_KEY", &client)?;
+        
+        Ok(Self {
+            client,
+            api_key: Some(api_key),
+            base_url: "https://api.openai.com/v1".to_string(),
+            headers: HashMap::new(),
+            models: vec![
+                "gpt-4".to_string(),
+                "gpt-4-turbo".to_string(),
+                "gpt-3.5-turbo".to_string(),
+            ],
+        })
+    }
+    
+    /// Build a provider for an openai-compatible server (vLLM, llama.cpp, LM Studio, gateways).
+    /// The API key is optional since most self-hosted servers don't require one.
+    pub fn compatible(name: &str, info: &ProviderInfo, client: HttpClient, credentials: &Credentials) -> Result<Self> {
+        let base_url = info.base_url.as_ref()
+            .ok_or_else(|| EvalError::ConfigError(
+                format!("Provider '{}' is openai-compatible but has no base_url", name)
             ))?;
         
-        let client = reqwest::Client::new();
-        Ok(Self { client, api_key })
+        let api_key = match &info.api_key_env {
+            Some(var) => Some(credentials.api_key(name, var, &client)?),
+            None => credentials.profile_key(name).map(str::to_string),
+        };
+        
+        Ok(Self {
+            client,
+            api_key,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            headers: info.headers.clone(),
+            models: info.available_models.clone(),
+        })
     }
-}
-
-#[async_trait]
-impl Provider for OpenAIProvider {
-    async fn execute(&self, prompt: &str, model: &str, settings: &ModelSettings) -> Result<ProviderResponse> {
-        let request_body = serde_json::json!({
+    
+    fn build_request(&self, messages: &[ChatMessage], model: &str, settings: &ModelSettings) -> serde_json::Value {
+        let messages: Vec<serde_json::Value> = messages.iter().map(openai_message).collect();
+        
+        let mut request_body = serde_json::json!({
             "model": model,
-            "messages": [
-                {"role": "user", "content": prompt}
-            ],
-            "temperature": settings.temperature.unwrap_or(0.7),
-            "max_tokens": settings.max_tokens.unwrap_or(1000),
+            "messages": messages,
+            "temperature": settings.temperature.unwrap_or(DEFAULT_TEMPERATURE),
+            "max_tokens": settings.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS),
         });
         
-        let response = self.client
-            .post("https://api.openai.com/v1/chat/completions")
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json")
-            .json(&request_body)
-            .send()
-            .await
-            .map_err(|e| crate::utils::error::EvalError::ProviderError(e.to_string()))?;
+        set_optional(&mut request_body, "top_p", settings.top_p);
+        set_optional(&mut request_body, "stop", Some(&settings.stop).filter(|stop| !stop.is_empty()));
+        set_optional(&mut request_body, "seed", settings.seed);
+        set_optional(&mut request_body, "presence_penalty", settings.presence_penalty);
+        set_optional(&mut request_body, "frequency_penalty", settings.frequency_penalty);
+        merge_extra(&mut request_body, &settings.extra);
+        
+        request_body
+    }
+    
+    /// Configured headers plus the bearer token, when there is an API key
+    fn request_headers<'a>(&'a self, authorization: &'a Option<String>) -> Vec<(&'a str, &'a str)> {
+        let mut headers: Vec<(&str, &str)> = self.headers.iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect();
+        if let Some(authorization) = authorization {
+            headers.push(("Authorization", authorization.as_str()));
+        }
+        headers
+    }
+    
+    async fn send(&self, request_body: &serde_json::Value) -> Result<HttpResponse> {
+        let authorization = self.api_key.as_ref().map(|key| format!("Bearer {}", key));
+        let url = format!("{}/chat/completions", self.base_url);
+        self.client.post_json(&url, &self.request_headers(&authorization), request_body).await
+    }
+    
+    async fn complete(&self, request_body: &serde_json::Value, messages: &[ChatMessage]) -> Result<ProviderResponse> {
+        let response = check_status(self.send(request_body).await?).await?;
+        
+        let response_json = response.json().await?;
         
-        let response_json: serde_json::Value = response.json().await
-            .map_err(|e| crate::utils::error::EvalError::ProviderError(e.to_string()))?;
+        let choice = &response_json["choices"][0];
+        if choice["finish_reason"] == "content_filter" {
+            return Err(content_filtered("finish_reason: content_filter"));
+        }
         
-        let content = response_json["choices"][0]["message"]["content"]
-            .as_str()
-            .unwrap_or("")
-            .to_string();
+        // Content is null when the model only calls tools
+        let tool_calls = openai_tool_calls(&choice["message"]);
+        let content = match choice["message"]["content"].as_str() {
+            Some(content) => content.to_string(),
+            None if !tool_calls.is_empty() => String::new(),
+            None => return Err(empty_response("choices[0].message.content")),
+        };
         
+        // Some OpenAI-compatible servers omit usage; those counts are estimated
         let usage = &response_json["usage"];
-        let input_tokens = usage["prompt_tokens"].as_u64().unwrap_or(0) as u32;
-        let output_tokens = usage["completion_tokens"].as_u64().unwrap_or(0) as u32;
+        let input_tokens = usage["prompt_tokens"].as_u64().map(|n| n as u32);
+        let output_tokens = usage["completion_tokens"].as_u64().map(|n| n as u32);
+        let cached_input_tokens = usage["prompt_tokens_details"]["cached_tokens"].as_u64().unwrap_or(0) as u32;
+        let usage = Usage::from_counts(input_tokens, output_tokens, cached_input_tokens, messages, &content);
         
         Ok(ProviderResponse {
             content,
-            usage: Usage {
-                input_tokens,
-                output_tokens,
-                total_tokens: input_tokens + output_tokens,
-            },
+            usage,
             metadata: HashMap::new(),
+            timing: None,
+            tool_calls,
         })
     }
+}
+
+#[async_trait]
+impl Provider for OpenAIProvider {
+    async fn execute(&self, messages: &[ChatMessage], model: &str, settings: &ModelSettings) -> Result<ProviderResponse> {
+        self.execute_with_tools(messages, &[], model, settings).await
+    }
+    
+    async fn execute_with_tools(&self, messages: &[ChatMessage], tools: &[ToolDefinition], model: &str, settings: &ModelSettings) -> Result<ProviderResponse> {
+        let mut request_body = self.build_request(messages, model, settings);
+        if !tools.is_empty() {
+            request_body["tools"] = openai_tools(tools);
+        }
+        self.complete(&request_body, messages).await
+    }
+    
+    async fn execute_structured(&self, messages: &[ChatMessage], schema: &serde_json::Value, model: &str, settings: &ModelSettings) -> Result<ProviderResponse> {
+        let mut request_body = self.build_request(messages, model, settings);
+        request_body["response_format"] = serde_json::json!({
+            "type": "json_schema",
+            "json_schema": {"name": "response", "schema": schema},
+        });
+        self.complete(&request_body, messages).await
+    }
     
     fn get_models(&self) -> Vec<String> {
-        vec![
-            "gpt-4".to_string(),
-            "gpt-4-turbo".to_string(),
-            "gpt-3.5-turbo".to_string(),
-        ]
+        self.models.clone()
     }
     
-    fn estimate_cost(&self, model: &str, input_tokens: u32, output_tokens: u32) -> f64 {
-        let pricing = match model {
-            "gpt-4" => (0.03, 0.06),
-            "gpt-4-turbo" => (0.01, 0.03),
-            "gpt-3.5-turbo" => (0.001, 0.002),
-            _ => (0.01, 0.03),
-        };
+    /// Compatible servers that accept `top_k` can be sent it through `extra`
+    fn ignored_settings(&self) -> &'static [&'static str] {
+        &["top_k"]
+    }
+    
+    async fn list_models(&self) -> Result<Option<Vec<String>>> {
+        let authorization = self.api_key.as_ref().map(|key| format!("Bearer {}", key));
+        let url = format!("{}/models", self.base_url);
+        let response = check_status(self.client.get_json(&url, &self.request_headers(&authorization)).await?).await?;
+        
+        let response_json = response.json().await?;
+        let models = response_json["data"].as_array()
+            .ok_or_else(|| empty_response("data"))?
+            .iter()
+            .filter_map(|model| model["id"].as_str().map(|id| id.to_string()))
+            .collect();
+        
+        Ok(Some(models))
+    }
+    
+    fn supports_streaming(&self) -> bool { true }
+    
+    async fn execute_streaming(&self, messages: &[ChatMessage], model: &str, settings: &ModelSettings) -> Result<ProviderResponse> {
+        let mut request_body = self.build_request(messages, model, settings);
+        request_body["stream"] = serde_json::Value::Bool(true);
+        // Usage is only reported on the final chunk when explicitly requested
+        request_body["stream_options"] = serde_json::json!({"include_usage": true});
+        
+        let mut timer = StreamTimer::start();
+        let response = check_status(self.send(&request_body).await?).await?;
+        let mut lines = LineReader::new(response);
+        
+        let mut content = String::new();
+        let mut input_tokens = None;
+        let mut output_tokens = None;
+        let mut cached_input_tokens = 0;
         
-        let input_cost = (input_tokens as f64 / 1000.0) * pricing.0;
-        let output_cost = (output_tokens as f64 / 1000.0) * pricing.1;
+        while let Some(line) = lines.next_line().await? {
+            let data = match sse_data(&line) {
+                Some(data) => data,
+                None => continue,
+            };
+            if data == "[DONE]" {
+                break;
+            }
+            
+            let chunk: serde_json::Value = serde_json::from_str(data)?;
+            
+            if chunk["error"].is_object() {
+                return Err(EvalError::ProviderError(
+                    chunk["error"]["message"].as_str().unwrap_or("stream error").to_string()
+                ));
+            }
+            if chunk["choices"][0]["finish_reason"] == "content_filter" {
+                return Err(content_filtered("finish_reason: content_filter"));
+            }
+            
+            if let Some(delta) = chunk["choices"][0]["delta"]["content"].as_str() {
+                if !delta.is_empty() {
+                    timer.record_token();
+                    content.push_str(delta);
+                }
+            }
+            
+            if chunk["usage"].is_object() {
+                input_tokens = chunk["usage"]["prompt_tokens"].as_u64().map(|n| n as u32);
+                output_tokens = chunk["usage"]["completion_tokens"].as_u64().map(|n| n as u32);
+                cached_input_tokens = chunk["usage"]["prompt_tokens_details"]["cached_tokens"].as_u64().unwrap_or(0) as u32;
+            }
+        }
         
-        input_cost + output_cost
+        let usage = Usage::from_counts(input_tokens, output_tokens, cached_input_tokens, messages, &content);
+        
+        Ok(ProviderResponse {
+            content,
+            usage,
+            metadata: HashMap::new(),
+            timing: timer.finish(),
+            tool_calls: Vec::new(),
+        })
     }
 }
 
 // Anthropic Provider Implementation
 pub struct AnthropicProvider {
-    client: reqwest::Client,
+    client: HttpClient,
     api_key: String,
 }
 
+/// Tool Claude is forced to call for structured-output prompts
+const STRUCTURED_OUTPUT_TOOL: &str = "structured_output";
+
 impl AnthropicProvider {
-    pub async fn new() -> Result<Self> {
-        let api_key = std::env::var("ANTHROPIC_API_KEY")
-            .map_err(|_| crate::utils::error::EvalError::ConfigError(
-                "ANTHROPIC_API_KEY environment variable not found".to_string()
-            ))?;
-        
-        let client = reqwest::Client::new();
+    pub async fn new(client: HttpClient, credentials: &Credentials) -> Result<Self> {
+        let api_key = credentials.api_key("anthropic", "ANTHROPIC_API_KEY", &client)?;
         Ok(Self { client, api_key })
     }
-}
-
-#[async_trait]
-impl Provider for AnthropicProvider {
-    async fn execute(&self, prompt: &str, model: &str, settings: &ModelSettings) -> Result<ProviderResponse> {
-        let request_body = serde_json::json!({
+    
+    fn build_request(&self, messages: &[ChatMessage], model: &str, settings: &ModelSettings) -> serde_json::Value {
+        let (system, turns) = split_system_prompt(messages);
+        let messages: Vec<serde_json::Value> = turns.iter()
+            .map(|m| {
+                if m.images.is_empty() {
+                    return serde_json::json!({"role": m.role.as_str(), "content": m.content});
+                }
+                
+                // Claude reads images best when they come before the question
+                let mut blocks: Vec<serde_json::Value> = m.images.iter()
+                    .map(|image| serde_json::json!({
+                        "type": "image",
+                        "source": {"type": "base64", "media_type": image.media_type, "data": image.data},
+                    }))
+                    .collect();
+                blocks.push(serde_json::json!({"type": "text", "text": m.content}));
+                serde_json::json!({"role": m.role.as_str(), "content": blocks})
+            })
+            .collect();
+        
+        let mut request_body = serde_json::json!({
             "model": model,
-            "max_tokens": settings.max_tokens.unwrap_or(1000),
-            "messages": [
-                {"role": "user", "content": prompt}
-            ]
+            "max_tokens": settings.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS),
+            "temperature": settings.temperature.unwrap_or(DEFAULT_TEMPERATURE),
+            "messages": messages,
         });
         
-        let response = self.client
-            .post("https://api.anthropic.com/v1/messages")
-            .header("x-api-key", &self.api_key)
-            .header("anthropic-version", "2023-06-01")
-            .header("Content-Type", "application/json")
-            .json(&request_body)
-            .send()
-            .await
-            .map_err(|e| crate::utils::error::EvalError::ProviderError(e.to_string()))?;
+        if let Some(system) = system {
+            request_body["system"] = serde_json::Value::String(system);
+        }
         
-        let response_json: serde_json::Value = response.json().await
-            .map_err(|e| crate::utils::error::EvalError::ProviderError(e.to_string()))?;
+        set_optional(&mut request_body, "top_p", settings.top_p);
+        set_optional(&mut request_body, "top_k", settings.top_k);
+        set_optional(&mut request_body, "stop_sequences", Some(&settings.stop).filter(|stop| !stop.is_empty()));
+        merge_extra(&mut request_body, &settings.extra);
         
-        let content = response_json["content"][0]["text"]
-            .as_str()
-            .unwrap_or("")
-            .to_string();
+        request_body
+    }
+    
+    async fn send(&self, request_body: &serde_json::Value) -> Result<HttpResponse> {
+        let headers = [
+            ("x-api-key", self.api_key.as_str()),
+            ("anthropic-version", "2023-06-01"),
+        ];
         
-        let usage = &response_json["usage"];
-        let input_tokens = usage["input_tokens"].as_u64().unwrap_or(0) as u32;
-        let output_tokens = usage["output_tokens"].as_u64().unwrap_or(0) as u32;
+        self.client.post_json("https://api.anthropic.com/v1/messages", &headers, request_body).await
+    }
+    
+    async fn complete(&self, request_body: &serde_json::Value) -> Result<ProviderResponse> {
+        let response = check_status(self.send(request_body).await?).await?;
         
-        Ok(ProviderResponse {
+        let response_json = response.json().await?;
+        
+        // Text and tool_use blocks can be interleaved
+        let blocks = response_json["content"].as_array().cloned().unwrap_or_default();
+        let content: String = blocks.iter()
+            .filter(|block| block["type"] == "text")
+            .filter_map(|block| block["text"].as_str())
+            .collect();
+        let tool_calls: Vec<ToolCall> = blocks.iter()
+            .filter(|block| block["type"] == "tool_use")
+            .filter_map(|block| Some(ToolCall {
+                id: block["id"].as_str().map(|id| id.to_string()),
+                name: block["name"].as_str()?.to_string(),
+                arguments: block["input"].clone(),
+            }))
+            .collect();
+        
+        if content.is_empty() && tool_calls.is_empty() {
+            return Err(empty_response("content[0].text"));
+        }
+        
+        let usage = &response_json["usage"];
+        let (input_tokens, cached_input_tokens, cache_write_input_tokens) = anthropic_input_tokens(usage);
+        let output_tokens = usage["output_tokens"].as_u64().unwrap_or(0) as u32;
+        
+        Ok(ProviderResponse {
             content,
-            usage: Usage {
-                input_tokens,
-                output_tokens,
-                total_tokens: input_tokens + output_tokens,
-            },
+            usage: Usage::reported(input_tokens, output_tokens, cached_input_tokens)
+                .with_cache_write_tokens(cache_write_input_tokens),
             metadata: HashMap::new(),
+            timing: None,
+            tool_calls,
         })
     }
+}
+
+#[async_trait]
+impl Provider for AnthropicProvider {
+    async fn execute(&self, messages: &[ChatMessage], model: &str, settings: &ModelSettings) -> Result<ProviderResponse> {
+        self.execute_with_tools(messages, &[], model, settings).await
+    }
+    
+    async fn execute_with_tools(&self, messages: &[ChatMessage], tools: &[ToolDefinition], model: &str, settings: &ModelSettings) -> Result<ProviderResponse> {
+        let mut request_body = self.build_request(messages, model, settings);
+        if !tools.is_empty() {
+            request_body["tools"] = tools.iter()
+                .map(|tool| serde_json::json!({
+                    "name": tool.name,
+                    "description": tool.description.clone().unwrap_or_default(),
+                    "input_schema": tool.parameters,
+                }))
+                .collect();
+        }
+        self.complete(&request_body).await
+    }
+    
+    /// Claude has no JSON mode, so the schema becomes a tool the model is forced to call
+    /// and the call's input is the output. Only object schemas can be tool inputs.
+    async fn execute_structured(&self, messages: &[ChatMessage], schema: &serde_json::Value, model: &str, settings: &ModelSettings) -> Result<ProviderResponse> {
+        if schema["type"] != "object" {
+            return self.execute(messages, model, settings).await;
+        }
+        
+        let mut request_body = self.build_request(messages, model, settings);
+        request_body["tools"] = serde_json::json!([{
+            "name": STRUCTURED_OUTPUT_TOOL,
+            "description": "Respond with the requested structured output",
+            "input_schema": schema,
+        }]);
+        request_body["tool_choice"] = serde_json::json!({"type": "tool", "name": STRUCTURED_OUTPUT_TOOL});
+        
+        let mut response = self.complete(&request_body).await?;
+        if let Some(index) = response.tool_calls.iter().position(|call| call.name == STRUCTURED_OUTPUT_TOOL) {
+            let call = response.tool_calls.remove(index);
+            response.content = call.arguments.to_string();
+        }
+        Ok(response)
+    }
     
     fn get_models(&self) -> Vec<String> {
         vec![
@@ -283,85 +1248,207 @@ impl Provider for AnthropicProvider {
         ]
     }
     
-    fn estimate_cost(&self, model: &str, input_tokens: u32, output_tokens: u32) -> f64 {
-        let pricing = match model {
-            "claude-3-opus-20240229" => (0.015, 0.075),
-            "claude-3-sonnet-20240229" => (0.003, 0.015),
-            "claude-3-haiku-20240307" => (0.00025, 0.00125),
-            _ => (0.003, 0.015),
-        };
+    fn ignored_settings(&self) -> &'static [&'static str] {
+        &["seed", "presence_penalty", "frequency_penalty"]
+    }
+    
+    fn supports_streaming(&self) -> bool { true }
+    
+    async fn execute_streaming(&self, messages: &[ChatMessage], model: &str, settings: &ModelSettings) -> Result<ProviderResponse> {
+        let mut request_body = self.build_request(messages, model, settings);
+        request_body["stream"] = serde_json::Value::Bool(true);
+        
+        let mut timer = StreamTimer::start();
+        let response = check_status(self.send(&request_body).await?).await?;
+        let mut lines = LineReader::new(response);
         
-        let input_cost = (input_tokens as f64 / 1000.0) * pricing.0;
-        let output_cost = (output_tokens as f64 / 1000.0) * pricing.1;
+        let mut content = String::new();
+        let mut input_tokens = 0;
+        let mut output_tokens = 0;
+        let mut cached_input_tokens = 0;
+        let mut cache_write_input_tokens = 0;
+        
+        while let Some(line) = lines.next_line().await? {
+            let data = match sse_data(&line) {
+                Some(data) => data,
+                None => continue,
+            };
+            
+            let event: serde_json::Value = serde_json::from_str(data)?;
+            
+            match event["type"].as_str() {
+                Some("message_start") => {
+                    (input_tokens, cached_input_tokens, cache_write_input_tokens) = anthropic_input_tokens(&event["message"]["usage"]);
+                },
+                Some("content_block_delta") => {
+                    if let Some(text) = event["delta"]["text"].as_str() {
+                        if !text.is_empty() {
+                            timer.record_token();
+                            content.push_str(text);
+                        }
+                    }
+                },
+                Some("message_delta") => {
+                    output_tokens = event["usage"]["output_tokens"].as_u64().unwrap_or(0) as u32;
+                },
+                Some("error") => {
+                    let message = event["error"]["message"].as_str().unwrap_or("stream error");
+                    let kind = match event["error"]["type"].as_str() {
+                        Some("overloaded_error") | Some("api_error") => ApiErrorKind::ServerError,
+                        Some("rate_limit_error") => ApiErrorKind::RateLimited,
+                        Some("authentication_error") | Some("permission_error") => ApiErrorKind::Authentication,
+                        _ => ApiErrorKind::InvalidRequest,
+                    };
+                    return Err(EvalError::ApiError(ApiError::new(kind, message)));
+                },
+                Some("message_stop") => break,
+                _ => {},
+            }
+        }
         
-        input_cost + output_cost
+        Ok(ProviderResponse {
+            content,
+            usage: Usage::reported(input_tokens, output_tokens, cached_input_tokens)
+                .with_cache_write_tokens(cache_write_input_tokens),
+            metadata: HashMap::new(),
+            timing: timer.finish(),
+            tool_calls: Vec::new(),
+        })
     }
 }
 
 // Google Provider Implementation
 pub struct GoogleProvider {
-    client: reqwest::Client,
+    client: HttpClient,
     api_key: String,
 }
 
 impl GoogleProvider {
-    pub async fn new() -> Result<Self> {
-        let api_key = std::env::var("GOOGLE_API_KEY")
-            .map_err(|_| crate::utils::error::EvalError::ConfigError(
-                "GOOGLE_API_KEY environment variable not found".to_string()
-            ))?;
-        
-        let client = reqwest::Client::new();
+    pub async fn new(client: HttpClient, credentials: &Credentials) -> Result<Self> {
+        let api_key = credentials.api_key("google", "GOOGLE_API_KEY", &client)?;
         Ok(Self { client, api_key })
     }
-}
-
-#[async_trait]
-impl Provider for GoogleProvider {
-    async fn execute(&self, prompt: &str, model: &str, settings: &ModelSettings) -> Result<ProviderResponse> {
-        let request_body = serde_json::json!({
-            "contents": [{
-                "parts": [{"text": prompt}]
-            }],
+    
+    fn build_request(&self, messages: &[ChatMessage], settings: &ModelSettings) -> serde_json::Value {
+        let (system, turns) = split_system_prompt(messages);
+        let contents: Vec<serde_json::Value> = turns.iter()
+            .map(|m| {
+                // Gemini calls the assistant role "model"
+                let role = match m.role {
+                    MessageRole::Assistant => "model",
+                    _ => "user",
+                };
+                let mut parts = vec![serde_json::json!({"text": m.content})];
+                parts.extend(m.images.iter().map(|image| serde_json::json!({
+                    "inlineData": {"mimeType": image.media_type, "data": image.data},
+                })));
+                serde_json::json!({"role": role, "parts": parts})
+            })
+            .collect();
+        
+        let mut request_body = serde_json::json!({
+            "contents": contents,
             "generationConfig": {
-                "temperature": settings.temperature.unwrap_or(0.7),
-                "maxOutputTokens": settings.max_tokens.unwrap_or(1000),
+                "temperature": settings.temperature.unwrap_or(DEFAULT_TEMPERATURE),
+                "maxOutputTokens": settings.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS),
             }
         });
         
-        let url = format!("https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}", 
-            model, self.api_key);
+        if let Some(system) = system {
+            request_body["systemInstruction"] = serde_json::json!({"parts": [{"text": system}]});
+        }
         
-        let response = self.client
-            .post(&url)
-            .header("Content-Type", "application/json")
-            .json(&request_body)
-            .send()
-            .await
-            .map_err(|e| crate::utils::error::EvalError::ProviderError(e.to_string()))?;
+        let generation_config = &mut request_body["generationConfig"];
+        set_optional(generation_config, "topP", settings.top_p);
+        set_optional(generation_config, "topK", settings.top_k);
+        set_optional(generation_config, "stopSequences", Some(&settings.stop).filter(|stop| !stop.is_empty()));
+        set_optional(generation_config, "seed", settings.seed);
+        set_optional(generation_config, "presencePenalty", settings.presence_penalty);
+        set_optional(generation_config, "frequencyPenalty", settings.frequency_penalty);
+        merge_extra(&mut request_body, &settings.extra);
         
-        let response_json: serde_json::Value = response.json().await
-            .map_err(|e| crate::utils::error::EvalError::ProviderError(e.to_string()))?;
+        request_body
+    }
+    
+    async fn send(&self, url: &str, request_body: &serde_json::Value) -> Result<HttpResponse> {
+        // Key goes in a header rather than the query string so it never ends up in cassettes or logs
+        let headers = [("x-goog-api-key", self.api_key.as_str())];
+        self.client.post_json(url, &headers, request_body).await
+    }
+    
+    async fn complete(&self, url: &str, request_body: &serde_json::Value, messages: &[ChatMessage]) -> Result<ProviderResponse> {
+        let response = check_status(self.send(url, request_body).await?).await?;
         
-        let content = response_json["candidates"][0]["content"]["parts"][0]["text"]
-            .as_str()
-            .unwrap_or("")
-            .to_string();
+        let response_json = response.json().await?;
         
-        // Google doesn't provide detailed token usage in the response
-        let estimated_input_tokens = (prompt.len() / 4) as u32; // Rough estimation
-        let estimated_output_tokens = (content.len() / 4) as u32;
+        check_gemini_blocked(&response_json)?;
+        
+        // Text and functionCall parts can be mixed in one candidate
+        let parts = response_json["candidates"][0]["content"]["parts"].as_array().cloned().unwrap_or_default();
+        let content: String = parts.iter()
+            .filter_map(|part| part["text"].as_str())
+            .collect();
+        let tool_calls: Vec<ToolCall> = parts.iter()
+            .filter_map(|part| {
+                let call = &part["functionCall"];
+                Some(ToolCall {
+                    id: None,
+                    name: call["name"].as_str()?.to_string(),
+                    arguments: call["args"].clone(),
+                })
+            })
+            .collect();
+        
+        if content.is_empty() && tool_calls.is_empty() {
+            return Err(empty_response("candidates[0].content.parts[0].text"));
+        }
+        
+        let usage = gemini_usage(&response_json["usageMetadata"], messages, &content);
         
         Ok(ProviderResponse {
             content,
-            usage: Usage {
-                input_tokens: estimated_input_tokens,
-                output_tokens: estimated_output_tokens,
-                total_tokens: estimated_input_tokens + estimated_output_tokens,
-            },
+            usage,
             metadata: HashMap::new(),
+            timing: None,
+            tool_calls,
         })
     }
+}
+
+#[async_trait]
+impl Provider for GoogleProvider {
+    async fn execute(&self, messages: &[ChatMessage], model: &str, settings: &ModelSettings) -> Result<ProviderResponse> {
+        self.execute_with_tools(messages, &[], model, settings).await
+    }
+    
+    async fn execute_with_tools(&self, messages: &[ChatMessage], tools: &[ToolDefinition], model: &str, settings: &ModelSettings) -> Result<ProviderResponse> {
+        let mut request_body = self.build_request(messages, settings);
+        if !tools.is_empty() {
+            let declarations: Vec<serde_json::Value> = tools.iter()
+                .map(|tool| serde_json::json!({
+                    "name": tool.name,
+                    "description": tool.description.clone().unwrap_or_default(),
+                    "parameters": tool.parameters,
+                }))
+                .collect();
+            request_body["tools"] = serde_json::json!([{"functionDeclarations": declarations}]);
+        }
+        
+        let url = format!("https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent",
+            model);
+        
+        self.complete(&url, &request_body, messages).await
+    }
+    
+    async fn execute_structured(&self, messages: &[ChatMessage], schema: &serde_json::Value, model: &str, settings: &ModelSettings) -> Result<ProviderResponse> {
+        let mut request_body = self.build_request(messages, settings);
+        request_body["generationConfig"]["responseMimeType"] = serde_json::json!("application/json");
+        request_body["generationConfig"]["responseJsonSchema"] = schema.clone();
+        
+        let url = format!("https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent",
+            model);
+        self.complete(&url, &request_body, messages).await
+    }
     
     fn get_models(&self) -> Vec<String> {
         vec![
@@ -371,82 +1458,181 @@ impl Provider for GoogleProvider {
         ]
     }
     
-    fn estimate_cost(&self, model: &str, input_tokens: u32, output_tokens: u32) -> f64 {
-        let pricing = match model {
-            "gemini-1.5-pro" => (0.0035, 0.0105),
-            "gemini-1.5-flash" => (0.00035, 0.00105),
-            "gemini-pro" => (0.0005, 0.0015),
-            _ => (0.0005, 0.0015),
-        };
+    fn supports_streaming(&self) -> bool { true }
+    
+    async fn execute_streaming(&self, messages: &[ChatMessage], model: &str, settings: &ModelSettings) -> Result<ProviderResponse> {
+        let request_body = self.build_request(messages, settings);
         
-        let input_cost = (input_tokens as f64 / 1000.0) * pricing.0;
-        let output_cost = (output_tokens as f64 / 1000.0) * pricing.1;
+        let url = format!("https://generativelanguage.googleapis.com/v1beta/models/{}:streamGenerateContent?alt=sse",
+            model);
         
-        input_cost + output_cost
+        let mut timer = StreamTimer::start();
+        let response = check_status(self.send(&url, &request_body).await?).await?;
+        let mut lines = LineReader::new(response);
+        
+        let mut content = String::new();
+        let mut usage_metadata = serde_json::Value::Null;
+        
+        while let Some(line) = lines.next_line().await? {
+            let data = match sse_data(&line) {
+                Some(data) => data,
+                None => continue,
+            };
+            
+            let chunk: serde_json::Value = serde_json::from_str(data)?;
+            check_gemini_blocked(&chunk)?;
+            
+            // Each chunk carries running totals; the last one covers the whole response
+            if chunk["usageMetadata"].is_object() {
+                usage_metadata = chunk["usageMetadata"].clone();
+            }
+            
+            if let Some(text) = chunk["candidates"][0]["content"]["parts"][0]["text"].as_str() {
+                if !text.is_empty() {
+                    timer.record_token();
+                    content.push_str(text);
+                }
+            }
+        }
+        
+        let usage = gemini_usage(&usage_metadata, messages, &content);
+        
+        Ok(ProviderResponse {
+            content,
+            usage,
+            metadata: HashMap::new(),
+            timing: timer.finish(),
+            tool_calls: Vec::new(),
+        })
+    }
+}
+
+/// Anthropic reports cached prompt tokens separately from `input_tokens`.
+/// Returns the full prompt size, the part read from cache and the part written to it.
+fn anthropic_input_tokens(usage: &serde_json::Value) -> (u32, u32, u32) {
+    let uncached = usage["input_tokens"].as_u64().unwrap_or(0) as u32;
+    let cache_read = usage["cache_read_input_tokens"].as_u64().unwrap_or(0) as u32;
+    let cache_creation = usage["cache_creation_input_tokens"].as_u64().unwrap_or(0) as u32;
+    (uncached + cache_read + cache_creation, cache_read, cache_creation)
+}
+
+/// Gemini's `usageMetadata`; counts it leaves out are estimated
+fn gemini_usage(usage_metadata: &serde_json::Value, messages: &[ChatMessage], content: &str) -> Usage {
+    let input_tokens = usage_metadata["promptTokenCount"].as_u64().map(|n| n as u32);
+    let output_tokens = usage_metadata["candidatesTokenCount"].as_u64().map(|n| n as u32);
+    let cached_input_tokens = usage_metadata["cachedContentTokenCount"].as_u64().unwrap_or(0) as u32;
+    Usage::from_counts(input_tokens, output_tokens, cached_input_tokens, messages, content)
+}
+
+/// Gemini reports safety blocks in a 200 response rather than as an HTTP error
+fn check_gemini_blocked(response_json: &serde_json::Value) -> Result<()> {
+    if let Some(reason) = response_json["promptFeedback"]["blockReason"].as_str() {
+        return Err(content_filtered(&format!("prompt blocked: {}", reason)));
+    }
+    
+    match response_json["candidates"][0]["finishReason"].as_str() {
+        Some(reason @ ("SAFETY" | "BLOCKLIST" | "PROHIBITED_CONTENT" | "SPII" | "RECITATION")) => {
+            Err(content_filtered(&format!("finishReason: {}", reason)))
+        },
+        _ => Ok(()),
     }
 }
 
 // Local Provider Implementation (for local models like Ollama)
 pub struct LocalProvider {
-    client: reqwest::Client,
+    client: HttpClient,
     base_url: String,
 }
 
 impl LocalProvider {
-    pub async fn new() -> Result<Self> {
+    pub async fn new(client: HttpClient) -> Result<Self> {
         let base_url = std::env::var("LOCAL_MODEL_URL")
             .unwrap_or_else(|_| "http://localhost:11434".to_string());
         
-        let client = reqwest::Client::new();
         Ok(Self { client, base_url })
     }
-}
-
-#[async_trait]
-impl Provider for LocalProvider {
-    async fn execute(&self, prompt: &str, model: &str, settings: &ModelSettings) -> Result<ProviderResponse> {
-        let request_body = serde_json::json!({
+    
+    fn build_request(&self, messages: &[ChatMessage], model: &str, settings: &ModelSettings, stream: bool) -> serde_json::Value {
+        let chat_messages: Vec<serde_json::Value> = messages.iter()
+            .map(|m| {
+                let mut message = serde_json::json!({"role": m.role.as_str(), "content": m.content});
+                // Ollama takes bare base64 strings alongside the text
+                if !m.images.is_empty() {
+                    message["images"] = m.images.iter().map(|image| serde_json::json!(image.data)).collect();
+                }
+                message
+            })
+            .collect();
+        
+        let mut request_body = serde_json::json!({
             "model": model,
-            "prompt": prompt,
-            "stream": false,
+            "messages": chat_messages,
+            "stream": stream,
             "options": {
-                "temperature": settings.temperature.unwrap_or(0.7),
-                "num_predict": settings.max_tokens.unwrap_or(1000),
+                "temperature": settings.temperature.unwrap_or(DEFAULT_TEMPERATURE),
+                "num_predict": settings.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS),
             }
         });
         
-        let url = format!("{}/api/generate", self.base_url);
+        let options = &mut request_body["options"];
+        set_optional(options, "top_p", settings.top_p);
+        set_optional(options, "top_k", settings.top_k);
+        set_optional(options, "stop", Some(&settings.stop).filter(|stop| !stop.is_empty()));
+        set_optional(options, "seed", settings.seed);
+        set_optional(options, "presence_penalty", settings.presence_penalty);
+        set_optional(options, "frequency_penalty", settings.frequency_penalty);
+        merge_extra(&mut request_body, &settings.extra);
         
-        let response = self.client
-            .post(&url)
-            .header("Content-Type", "application/json")
-            .json(&request_body)
-            .send()
-            .await
-            .map_err(|e| crate::utils::error::EvalError::ProviderError(e.to_string()))?;
+        request_body
+    }
+    
+    async fn send(&self, request_body: &serde_json::Value) -> Result<HttpResponse> {
+        let url = format!("{}/api/chat", self.base_url);
+        self.client.post_json(&url, &[], request_body).await
+    }
+    
+    async fn complete(&self, request_body: &serde_json::Value, messages: &[ChatMessage]) -> Result<ProviderResponse> {
+        let response = check_status(self.send(request_body).await?).await?;
         
-        let response_json: serde_json::Value = response.json().await
-            .map_err(|e| crate::utils::error::EvalError::ProviderError(e.to_string()))?;
+        let response_json = response.json().await?;
         
-        let content = response_json["response"]
+        let content = response_json["message"]["content"]
             .as_str()
-            .unwrap_or("")
+            .ok_or_else(|| empty_response("message.content"))?
             .to_string();
         
-        // Local models typically don't provide token counts
-        let estimated_input_tokens = (prompt.len() / 4) as u32;
-        let estimated_output_tokens = (content.len() / 4) as u32;
+        let usage = ollama_usage(&response_json, messages, &content);
         
         Ok(ProviderResponse {
             content,
-            usage: Usage {
-                input_tokens: estimated_input_tokens,
-                output_tokens: estimated_output_tokens,
-                total_tokens: estimated_input_tokens + estimated_output_tokens,
-            },
+            usage,
             metadata: HashMap::new(),
+            timing: None,
+            tool_calls: openai_tool_calls(&response_json["message"]),
         })
     }
+}
+
+#[async_trait]
+impl Provider for LocalProvider {
+    async fn execute(&self, messages: &[ChatMessage], model: &str, settings: &ModelSettings) -> Result<ProviderResponse> {
+        self.execute_with_tools(messages, &[], model, settings).await
+    }
+    
+    async fn execute_with_tools(&self, messages: &[ChatMessage], tools: &[ToolDefinition], model: &str, settings: &ModelSettings) -> Result<ProviderResponse> {
+        let mut request_body = self.build_request(messages, model, settings, false);
+        if !tools.is_empty() {
+            request_body["tools"] = openai_tools(tools);
+        }
+        self.complete(&request_body, messages).await
+    }
+    
+    /// Ollama constrains generation to the schema passed as `format`
+    async fn execute_structured(&self, messages: &[ChatMessage], schema: &serde_json::Value, model: &str, settings: &ModelSettings) -> Result<ProviderResponse> {
+        let mut request_body = self.build_request(messages, model, settings, false);
+        request_body["format"] = schema.clone();
+        self.complete(&request_body, messages).await
+    }
     
     fn get_models(&self) -> Vec<String> {
         vec![
@@ -457,7 +1643,543 @@ impl Provider for LocalProvider {
         ]
     }
     
-    fn estimate_cost(&self, _model: &str, _input_tokens: u32, _output_tokens: u32) -> f64 {
-        0.0 // Local models are free
+    /// Models pulled into the local Ollama instance
+    async fn list_models(&self) -> Result<Option<Vec<String>>> {
+        let url = format!("{}/api/tags", self.base_url);
+        let response = check_status(self.client.get_json(&url, &[]).await?).await?;
+        
+        let response_json = response.json().await?;
+        let models = response_json["models"].as_array()
+            .ok_or_else(|| empty_response("models"))?
+            .iter()
+            .filter_map(|model| model["name"].as_str().map(|name| name.to_string()))
+            .collect();
+        
+        Ok(Some(models))
+    }
+    
+    fn supports_streaming(&self) -> bool { true }
+    
+    async fn execute_streaming(&self, messages: &[ChatMessage], model: &str, settings: &ModelSettings) -> Result<ProviderResponse> {
+        let request_body = self.build_request(messages, model, settings, true);
+        
+        let mut timer = StreamTimer::start();
+        let response = check_status(self.send(&request_body).await?).await?;
+        let mut lines = LineReader::new(response);
+        
+        let mut content = String::new();
+        let mut final_chunk = serde_json::Value::Null;
+        
+        // Ollama streams newline-delimited JSON objects
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            
+            let chunk: serde_json::Value = serde_json::from_str(&line)?;
+            
+            if let Some(error) = chunk["error"].as_str() {
+                return Err(EvalError::ProviderError(error.to_string()));
+            }
+            
+            if let Some(text) = chunk["message"]["content"].as_str() {
+                if !text.is_empty() {
+                    timer.record_token();
+                    content.push_str(text);
+                }
+            }
+            
+            // The final chunk carries the token counts
+            if chunk["done"].as_bool().unwrap_or(false) {
+                final_chunk = chunk;
+                break;
+            }
+        }
+        
+        let usage = ollama_usage(&final_chunk, messages, &content);
+        
+        Ok(ProviderResponse {
+            content,
+            usage,
+            metadata: HashMap::new(),
+            timing: timer.finish(),
+            tool_calls: Vec::new(),
+        })
+    }
+}
+
+/// Ollama's `prompt_eval_count`/`eval_count`. `prompt_eval_count` is left out when the
+/// prompt was served from Ollama's cache, so the input side is estimated in that case.
+fn ollama_usage(response_json: &serde_json::Value, messages: &[ChatMessage], content: &str) -> Usage {
+    let input_tokens = response_json["prompt_eval_count"].as_u64().map(|n| n as u32);
+    let output_tokens = response_json["eval_count"].as_u64().map(|n| n as u32);
+    Usage::from_counts(input_tokens, output_tokens, 0, messages, content)
+}
+
+// Process Provider Implementation
+// Runs a user-supplied executable and exchanges one JSON object per line over stdin/stdout.
+// See "Process Providers" in the README for the protocol.
+pub struct ProcessProvider {
+    name: String,
+    command: String,
+    args: Vec<String>,
+    models: Vec<String>,
+    process: tokio::sync::Mutex<Option<PluginProcess>>,
+    next_id: std::sync::atomic::AtomicU64,
+}
+
+struct PluginProcess {
+    // Held so the child is killed when the handle is dropped
+    _child: tokio::process::Child,
+    stdin: tokio::process::ChildStdin,
+    stdout: tokio::io::Lines<tokio::io::BufReader<tokio::process::ChildStdout>>,
+}
+
+#[derive(Debug, Serialize)]
+struct PluginRequest<'a> {
+    id: u64,
+    model: &'a str,
+    /// Rendered transcript, for plugins that take a single prompt string
+    prompt: String,
+    messages: &'a [ChatMessage],
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    tools: &'a [ToolDefinition],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_schema: Option<&'a serde_json::Value>,
+    settings: &'a ModelSettings,
+}
+
+#[derive(Debug, Deserialize)]
+struct PluginResponse {
+    id: u64,
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default)]
+    usage: Option<PluginUsage>,
+    #[serde(default)]
+    metadata: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    tool_calls: Vec<ToolCall>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+/// Either count may be left out; missing ones are estimated
+#[derive(Debug, Deserialize)]
+struct PluginUsage {
+    #[serde(default)]
+    input_tokens: Option<u32>,
+    #[serde(default)]
+    output_tokens: Option<u32>,
+}
+
+/// Drops the plugin process unless a round trip finishes. A failed round trip, or one
+/// cancelled by the timeout mid-write or mid-read, could leave half a request on the
+/// plugin's stdin, so the next request starts a fresh process instead.
+struct ProcessSlot<'a> {
+    process: &'a mut Option<PluginProcess>,
+    finished: bool,
+}
+
+impl Drop for ProcessSlot<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            *self.process = None;
+        }
+    }
+}
+
+impl ProcessProvider {
+    pub fn new(name: &str, info: &ProviderInfo) -> Result<Self> {
+        let command = info.command.clone()
+            .ok_or_else(|| EvalError::ConfigError(
+                format!("Provider '{}' is a process provider but has no command", name)
+            ))?;
+        
+        Ok(Self {
+            name: name.to_string(),
+            command,
+            args: info.args.clone(),
+            models: info.available_models.clone(),
+            process: tokio::sync::Mutex::new(None),
+            next_id: std::sync::atomic::AtomicU64::new(1),
+        })
+    }
+    
+    fn spawn(&self) -> Result<PluginProcess> {
+        let mut child = tokio::process::Command::new(&self.command)
+            .args(&self.args)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::inherit())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| EvalError::ProviderError(
+                format!("Failed to start '{}' for provider '{}': {}", self.command, self.name, e)
+            ))?;
+        
+        let stdin = child.stdin.take()
+            .ok_or_else(|| EvalError::ProviderError("Plugin stdin unavailable".to_string()))?;
+        let stdout = child.stdout.take()
+            .ok_or_else(|| EvalError::ProviderError("Plugin stdout unavailable".to_string()))?;
+        
+        use tokio::io::AsyncBufReadExt;
+        Ok(PluginProcess {
+            _child: child,
+            stdin,
+            stdout: tokio::io::BufReader::new(stdout).lines(),
+        })
+    }
+    
+    async fn round_trip(process: &mut PluginProcess, request_line: &str, id: u64) -> Result<PluginResponse> {
+        use tokio::io::AsyncWriteExt;
+        
+        process.stdin.write_all(request_line.as_bytes()).await?;
+        process.stdin.write_all(b"\n").await?;
+        process.stdin.flush().await?;
+        
+        loop {
+            let line = process.stdout.next_line().await?
+                .ok_or_else(|| EvalError::ProviderError(
+                    "Plugin process exited before responding".to_string()
+                ))?;
+            
+            if line.trim().is_empty() {
+                continue;
+            }
+            
+            let response: PluginResponse = serde_json::from_str(&line)
+                .map_err(|e| EvalError::ProviderError(
+                    format!("Invalid plugin response '{}': {}", line, e)
+                ))?;
+            
+            // Stale responses from an earlier, abandoned request are skipped
+            if response.id == id {
+                return Ok(response);
+            }
+        }
+    }
+    
+    async fn complete(&self, request: PluginRequest<'_>) -> Result<ProviderResponse> {
+        let id = request.id;
+        let messages = request.messages;
+        let request_line = serde_json::to_string(&request)?;
+        
+        // One request in flight per process; the plugin is restarted if it dies
+        let mut guard = self.process.lock().await;
+        if guard.is_none() {
+            *guard = Some(self.spawn()?);
+        }
+        
+        let mut slot = ProcessSlot { process: &mut guard, finished: false };
+        let process = slot.process.as_mut().expect("process spawned above");
+        let response = Self::round_trip(process, &request_line, id).await;
+        slot.finished = response.is_ok();
+        drop(slot);
+        drop(guard);
+        let response = response?;
+        
+        if let Some(error) = response.error {
+            return Err(EvalError::ProviderError(
+                format!("{}: {}", self.name, error)
+            ));
+        }
+        
+        let content = response.content.unwrap_or_default();
+        
+        // Plugins that don't report usage are estimated like any other provider
+        let usage = match response.usage {
+            Some(usage) => Usage::from_counts(usage.input_tokens, usage.output_tokens, 0, messages, &content),
+            None => Usage::from_counts(None, None, 0, messages, &content),
+        };
+        
+        Ok(ProviderResponse {
+            content,
+            usage,
+            metadata: response.metadata,
+            timing: None,
+            tool_calls: response.tool_calls,
+        })
+    }
+}
+
+#[async_trait]
+impl Provider for ProcessProvider {
+    async fn execute(&self, messages: &[ChatMessage], model: &str, settings: &ModelSettings) -> Result<ProviderResponse> {
+        self.execute_with_tools(messages, &[], model, settings).await
+    }
+    
+    async fn execute_with_tools(&self, messages: &[ChatMessage], tools: &[ToolDefinition], model: &str, settings: &ModelSettings) -> Result<ProviderResponse> {
+        self.complete(PluginRequest {
+            id: self.next_id.fetch_add(1, std::sync::atomic::Ordering::SeqCst),
+            model,
+            prompt: render_transcript(messages),
+            messages,
+            tools,
+            response_schema: None,
+            settings,
+        }).await
+    }
+    
+    async fn execute_structured(&self, messages: &[ChatMessage], schema: &serde_json::Value, model: &str, settings: &ModelSettings) -> Result<ProviderResponse> {
+        self.complete(PluginRequest {
+            id: self.next_id.fetch_add(1, std::sync::atomic::Ordering::SeqCst),
+            model,
+            prompt: render_transcript(messages),
+            messages,
+            tools: &[],
+            response_schema: Some(schema),
+            settings,
+        }).await
+    }
+    
+    fn get_models(&self) -> Vec<String> {
+        self.models.clone()
+    }
+}
+
+// Mock Provider Implementation
+// Deterministic scripted responses for CI and demos; never touches the network.
+pub struct MockProvider {
+    models: Vec<String>,
+    rules: Vec<MockRule>,
+}
+
+struct MockRule {
+    fixture: MockFixture,
+    regex: Option<regex::Regex>,
+    calls: std::sync::atomic::AtomicU32,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MockFixtureFile {
+    #[serde(default)]
+    pub models: Vec<String>,
+    #[serde(default)]
+    pub responses: Vec<MockFixture>,
+}
+
+/// One scripted response. All match fields that are set must match; the first matching entry wins.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MockFixture {
+    #[serde(default)]
+    pub model: Option<String>,
+    /// SHA-256 of the rendered prompt (reported as `prompt_hash` in result metadata)
+    #[serde(default)]
+    pub prompt_hash: Option<String>,
+    #[serde(default)]
+    pub prompt_regex: Option<String>,
+    #[serde(default)]
+    pub content: String,
+    #[serde(default)]
+    pub input_tokens: Option<u32>,
+    #[serde(default)]
+    pub output_tokens: Option<u32>,
+    #[serde(default)]
+    pub latency_ms: u64,
+    /// Fail every matching call with this message
+    #[serde(default)]
+    pub error: Option<String>,
+    /// Fail only the first N matching calls, then succeed
+    #[serde(default)]
+    pub fail_first: u32,
+    /// HTTP status to simulate for injected failures (e.g. 429, 503), so retries can be exercised
+    #[serde(default)]
+    pub error_status: Option<u16>,
+    /// Tool calls to return, for exercising tool-calling evaluations
+    #[serde(default)]
+    pub tool_calls: Vec<ToolCall>,
+}
+
+impl MockProvider {
+    pub async fn new(name: &str, info: &ProviderInfo) -> Result<Self> {
+        let fixture_file = match &info.fixtures {
+            Some(path) => {
+                let contents = tokio::fs::read_to_string(path).await?;
+                serde_json::from_str::<MockFixtureFile>(&contents)
+                    .map_err(|e| EvalError::ConfigError(
+                        format!("Invalid mock fixtures {} for provider '{}': {}", path, name, e)
+                    ))?
+            }
+            None => MockFixtureFile::default(),
+        };
+        
+        let mut rules = Vec::new();
+        for fixture in fixture_file.responses {
+            let regex = match &fixture.prompt_regex {
+                Some(pattern) => Some(regex::Regex::new(pattern)
+                    .map_err(|e| EvalError::ConfigError(
+                        format!("Invalid prompt_regex '{}' in mock fixtures: {}", pattern, e)
+                    ))?),
+                None => None,
+            };
+            rules.push(MockRule {
+                fixture,
+                regex,
+                calls: std::sync::atomic::AtomicU32::new(0),
+            });
+        }
+        
+        let mut models = info.available_models.clone();
+        for model in fixture_file.models {
+            if !models.contains(&model) {
+                models.push(model);
+            }
+        }
+        
+        Ok(Self {
+            models,
+            rules,
+        })
+    }
+    
+    fn find_rule(&self, model: &str, prompt: &str, hash: &str) -> Option<&MockRule> {
+        self.rules.iter().find(|rule| {
+            !matches!(rule.fixture.model.as_deref(), Some(m) if m != model)
+                && !matches!(rule.fixture.prompt_hash.as_deref(), Some(h) if h != hash)
+                && !matches!(&rule.regex, Some(r) if !r.is_match(prompt))
+        })
+    }
+}
+
+#[async_trait]
+impl Provider for MockProvider {
+    async fn execute(&self, messages: &[ChatMessage], model: &str, _settings: &ModelSettings) -> Result<ProviderResponse> {
+        let prompt = render_transcript(messages);
+        let hash = prompt_hash(messages);
+        
+        let mut metadata = HashMap::new();
+        metadata.insert("prompt_hash".to_string(), serde_json::json!(hash));
+        
+        // Without a matching fixture the prompt is echoed back
+        let rule = match self.find_rule(model, &prompt, &hash) {
+            Some(rule) => rule,
+            None => {
+                metadata.insert("mock_match".to_string(), serde_json::json!("echo"));
+                let usage = Usage::from_counts(None, None, 0, messages, &prompt);
+                return Ok(ProviderResponse {
+                    content: prompt,
+                    usage,
+                    metadata,
+                    timing: None,
+                    tool_calls: Vec::new(),
+                });
+            }
+        };
+        
+        let call = rule.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        let fixture = &rule.fixture;
+        
+        if fixture.latency_ms > 0 {
+            tokio::time::sleep(std::time::Duration::from_millis(fixture.latency_ms)).await;
+        }
+        
+        let injected = match &fixture.error {
+            Some(error) => Some(error.clone()),
+            None if call < fixture.fail_first => {
+                Some(format!("Injected mock failure ({} of {})", call + 1, fixture.fail_first))
+            },
+            None => None,
+        };
+        if let Some(message) = injected {
+            return Err(match fixture.error_status {
+                Some(status) => EvalError::ApiError(ApiError::from_response(status, &message, None)),
+                None => EvalError::ProviderError(message),
+            });
+        }
+        
+        let usage = Usage::from_counts(fixture.input_tokens, fixture.output_tokens, 0, messages, &fixture.content);
+        metadata.insert("mock_match".to_string(), serde_json::json!("fixture"));
+        
+        Ok(ProviderResponse {
+            content: fixture.content.clone(),
+            usage,
+            metadata,
+            timing: None,
+            tool_calls: fixture.tool_calls.clone(),
+        })
+    }
+    
+    /// Tools are accepted but not inspected; fixtures script the calls
+    async fn execute_with_tools(&self, messages: &[ChatMessage], _tools: &[ToolDefinition], model: &str, settings: &ModelSettings) -> Result<ProviderResponse> {
+        self.execute(messages, model, settings).await
+    }
+    
+    fn get_models(&self) -> Vec<String> {
+        self.models.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[tokio::test]
+    async fn unknown_builtin_entries_are_skipped_with_a_reason() {
+        let mut config = EvalEdsConfig::default();
+        let mut entry = config.providers["openai"].clone();
+        entry.enabled = true;
+        config.providers.insert("opnai".to_string(), entry);
+        
+        let manager = ProviderManager::from_config(HttpClient::live(), config, Credentials::default()).await.unwrap();
+        assert_eq!(manager.skipped.get("opnai").map(String::as_str), Some("not a built-in provider; set kind"));
+        assert!(!manager.providers.contains_key("opnai"));
+        assert_eq!(manager.skipped.get("local").map(String::as_str), Some("disabled in config"));
+    }
+    
+    #[test]
+    fn anthropic_cache_writes_are_reported_separately() {
+        let usage = serde_json::json!({
+            "input_tokens": 50,
+            "cache_read_input_tokens": 1000,
+            "cache_creation_input_tokens": 200,
+        });
+        assert_eq!(anthropic_input_tokens(&usage), (1250, 1000, 200));
+        assert_eq!(anthropic_input_tokens(&serde_json::json!({"input_tokens": 50})), (50, 0, 0));
+    }
+    
+    #[test]
+    fn retry_after_overrides_backoff() {
+        assert_eq!(backoff_delay(1, Some(Duration::from_secs(7))), Duration::from_secs(7));
+        assert_eq!(backoff_delay(5, Some(Duration::from_millis(250))), Duration::from_millis(250));
+    }
+    
+    #[test]
+    fn backoff_doubles_with_jitter() {
+        for attempt in 1..=4u32 {
+            let base = Duration::from_millis(1000 << (attempt - 1));
+            for _ in 0..50 {
+                let delay = backoff_delay(attempt, None);
+                assert!(delay >= base / 2 && delay <= base, "attempt {}: {:?}", attempt, delay);
+            }
+        }
+    }
+    
+    #[test]
+    fn backoff_is_capped_at_a_minute() {
+        for attempt in [7, 20, u32::MAX] {
+            let delay = backoff_delay(attempt, None);
+            assert!(delay >= Duration::from_secs(30) && delay <= Duration::from_secs(60));
+        }
+        // Attempt 0 is treated like the first retry rather than underflowing
+        assert!(backoff_delay(0, None) <= Duration::from_secs(1));
+    }
+    
+    #[test]
+    fn transient_errors_are_retryable() {
+        let rate_limited = EvalError::ApiError(ApiError::from_response(429, "slow down", Some(Duration::from_secs(2))));
+        assert!(is_retryable(&rate_limited));
+        assert_eq!(retry_after(&rate_limited), Some(Duration::from_secs(2)));
+        
+        assert!(is_retryable(&EvalError::ApiError(ApiError::from_response(502, "bad gateway", None))));
+        assert!(is_retryable(&EvalError::NetworkError("connection reset".to_string())));
+    }
+    
+    #[test]
+    fn permanent_errors_are_not_retried() {
+        assert!(!is_retryable(&EvalError::ApiError(ApiError::from_response(401, "invalid key", None))));
+        assert!(!is_retryable(&EvalError::ApiError(ApiError::from_response(400, "maximum context length", None))));
+        assert!(!is_retryable(&EvalError::ProviderError("Request timed out after 30s".to_string())));
+        assert_eq!(retry_after(&EvalError::NetworkError("reset".to_string())), None);
     }
 }
\ No newline at end of file
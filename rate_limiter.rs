@@ -0,0 +1,206 @@
+// Token-bucket rate limiting for provider requests
+use crate::utils::config::RateLimits;
+use std::time::{Duration, Instant};
+
+/// Per-provider limiter covering both requests per minute and tokens per minute.
+/// A limit of 0 disables that bucket.
+pub struct RateLimiter {
+    state: tokio::sync::Mutex<Buckets>,
+}
+
+struct Buckets {
+    requests: Option<Bucket>,
+    tokens: Option<Bucket>,
+}
+
+struct Bucket {
+    capacity: f64,
+    available: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl Bucket {
+    fn per_minute(limit: u32) -> Option<Self> {
+        if limit == 0 {
+            return None;
+        }
+        
+        let capacity = limit as f64;
+        Some(Self {
+            capacity,
+            available: capacity,
+            refill_per_sec: capacity / 60.0,
+            last_refill: Instant::now(),
+        })
+    }
+    
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.available = (self.available + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+    }
+    
+    /// Time until `amount` is available; requests larger than the bucket only wait for a full bucket
+    fn wait_for(&self, amount: f64) -> Duration {
+        let needed = amount.min(self.capacity) - self.available;
+        if needed <= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(needed / self.refill_per_sec)
+        }
+    }
+}
+
+impl RateLimiter {
+    pub fn new(limits: &RateLimits) -> Self {
+        Self {
+            state: tokio::sync::Mutex::new(Buckets {
+                requests: Bucket::per_minute(limits.requests_per_minute),
+                tokens: Bucket::per_minute(limits.tokens_per_minute),
+            }),
+        }
+    }
+    
+    /// Wait until one request and `estimated_tokens` fit in the buckets, then take them.
+    /// `on_wait` is called before each sleep so callers can surface throttling.
+    /// Returns the total time spent waiting.
+    pub async fn acquire(&self, estimated_tokens: u32, on_wait: impl Fn(Duration)) -> Duration {
+        let started = Instant::now();
+        
+        loop {
+            let wait = {
+                let mut state = self.state.lock().await;
+                let now = Instant::now();
+                
+                let mut wait = Duration::ZERO;
+                if let Some(requests) = state.requests.as_mut() {
+                    requests.refill(now);
+                    wait = wait.max(requests.wait_for(1.0));
+                }
+                if let Some(tokens) = state.tokens.as_mut() {
+                    tokens.refill(now);
+                    wait = wait.max(tokens.wait_for(estimated_tokens as f64));
+                }
+                
+                if wait.is_zero() {
+                    if let Some(requests) = state.requests.as_mut() {
+                        requests.available -= 1.0;
+                    }
+                    if let Some(tokens) = state.tokens.as_mut() {
+                        tokens.available -= (estimated_tokens as f64).min(tokens.capacity);
+                    }
+                    return started.elapsed();
+                }
+                
+                wait
+            };
+            
+            on_wait(wait);
+            tokio::time::sleep(wait).await;
+        }
+    }
+    
+    /// Correct the token bucket once the real usage is known. Under-estimates leave the
+    /// bucket in debt so later requests wait; over-estimates are refunded.
+    pub async fn settle(&self, estimated_tokens: u32, actual_tokens: u32) {
+        let mut state = self.state.lock().await;
+        if let Some(tokens) = state.tokens.as_mut() {
+            let charged = (estimated_tokens as f64).min(tokens.capacity);
+            tokens.available = (tokens.available + charged - actual_tokens as f64).min(tokens.capacity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn limiter(requests_per_minute: u32, tokens_per_minute: u32) -> RateLimiter {
+        RateLimiter::new(&RateLimits { requests_per_minute, tokens_per_minute })
+    }
+    
+    async fn available_tokens(limiter: &RateLimiter) -> f64 {
+        limiter.state.lock().await.tokens.as_ref().unwrap().available
+    }
+    
+    #[test]
+    fn zero_limit_disables_the_bucket() {
+        assert!(Bucket::per_minute(0).is_none());
+    }
+    
+    #[test]
+    fn full_bucket_does_not_wait() {
+        let bucket = Bucket::per_minute(60).unwrap();
+        assert_eq!(bucket.wait_for(1.0), Duration::ZERO);
+        assert_eq!(bucket.wait_for(60.0), Duration::ZERO);
+    }
+    
+    #[test]
+    fn exhausted_bucket_waits_for_refill() {
+        let mut bucket = Bucket::per_minute(60).unwrap();
+        bucket.available = 0.0;
+        assert_eq!(bucket.wait_for(1.0), Duration::from_secs(1));
+        assert_eq!(bucket.wait_for(30.0), Duration::from_secs(30));
+    }
+    
+    #[test]
+    fn oversized_request_waits_only_for_a_full_bucket() {
+        let mut bucket = Bucket::per_minute(60).unwrap();
+        assert_eq!(bucket.wait_for(1000.0), Duration::ZERO);
+        
+        bucket.available = 0.0;
+        assert_eq!(bucket.wait_for(1000.0), Duration::from_secs(60));
+    }
+    
+    #[test]
+    fn refill_is_capped_at_capacity() {
+        let mut bucket = Bucket::per_minute(60).unwrap();
+        bucket.available = 0.0;
+        let start = bucket.last_refill;
+        
+        bucket.refill(start + Duration::from_secs(10));
+        assert!((bucket.available - 10.0).abs() < 1e-9);
+        
+        bucket.refill(start + Duration::from_secs(600));
+        assert_eq!(bucket.available, 60.0);
+    }
+    
+    #[tokio::test]
+    async fn acquire_within_budget_returns_immediately() {
+        let limiter = limiter(60, 1000);
+        let waited = limiter.acquire(400, |_| panic!("should not wait")).await;
+        
+        assert!(waited < Duration::from_millis(100));
+        assert!(available_tokens(&limiter).await <= 600.5);
+    }
+    
+    #[tokio::test]
+    async fn acquire_without_limits_never_waits() {
+        let limiter = limiter(0, 0);
+        for _ in 0..1000 {
+            limiter.acquire(u32::MAX, |_| panic!("should not wait")).await;
+        }
+    }
+    
+    #[tokio::test]
+    async fn settle_refunds_over_estimates_and_charges_under_estimates() {
+        let limiter = limiter(0, 1000);
+        limiter.acquire(400, |_| {}).await;
+        
+        limiter.settle(400, 100).await;
+        let refunded = available_tokens(&limiter).await;
+        assert!((refunded - 900.0).abs() < 1.0);
+        
+        limiter.acquire(100, |_| {}).await;
+        limiter.settle(100, 1500).await;
+        assert!(available_tokens(&limiter).await < 0.0);
+    }
+    
+    #[tokio::test]
+    async fn settle_never_overfills_the_bucket() {
+        let limiter = limiter(0, 1000);
+        limiter.settle(1000, 0).await;
+        assert_eq!(available_tokens(&limiter).await, 1000.0);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,121 @@
+// Local cache of provider responses, so re-runs don't pay for the same completion twice
+use crate::core::evaluation::{ChatMessage, ExecutionMetadata, ModelSettings, ToolCall, ToolDefinition, prompt_hash};
+use crate::utils::error::{Result, EvalError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+
+/// How a run uses the response cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheMode {
+    /// Serve fresh entries and store new responses
+    ReadWrite,
+    /// Skip lookups but store new responses, replacing existing entries
+    Refresh,
+}
+
+/// Content-addressed store of successful responses, one JSON file per request in
+/// `~/.evaleds/response_cache`
+pub struct ResponseCache {
+    dir: PathBuf,
+    ttl: Option<chrono::Duration>,
+    mode: CacheMode,
+}
+
+/// A stored response with the metadata it was first recorded with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedResponse {
+    pub provider: String,
+    pub model: String,
+    pub stored_at: DateTime<Utc>,
+    pub output: String,
+    #[serde(default)]
+    pub tool_calls: Vec<ToolCall>,
+    pub metadata: ExecutionMetadata,
+}
+
+impl ResponseCache {
+    pub fn default_dir() -> Result<PathBuf> {
+        let home_dir = dirs::home_dir()
+            .ok_or_else(|| EvalError::ConfigError("Could not find home directory".to_string()))?;
+        
+        Ok(home_dir.join(".evaleds").join("response_cache"))
+    }
+    
+    /// `ttl_hours` of 0 keeps entries forever
+    pub fn new(dir: impl Into<PathBuf>, ttl_hours: u64, mode: CacheMode) -> Self {
+        Self {
+            dir: dir.into(),
+            ttl: (ttl_hours > 0).then(|| chrono::Duration::hours(ttl_hours as i64)),
+            mode,
+        }
+    }
+    
+    /// Hash of everything that shapes the request. Only cells sampled once are cached,
+    /// so the repetition is not part of the key.
+    pub fn key(
+        provider: &str,
+        model: &str,
+        messages: &[ChatMessage],
+        tools: &[ToolDefinition],
+        response_schema: Option<&serde_json::Value>,
+        settings: &ModelSettings,
+    ) -> String {
+        // Converting to Value sorts object keys, so passthrough settings hash the same every run
+        let canonical = |value: serde_json::Result<serde_json::Value>| {
+            value.map(|v| v.to_string()).unwrap_or_default()
+        };
+        
+        let mut hasher = Sha256::new();
+        for part in [
+            provider.to_string(),
+            model.to_string(),
+            prompt_hash(messages),
+            canonical(serde_json::to_value(tools)),
+            canonical(serde_json::to_value(response_schema)),
+            canonical(serde_json::to_value(settings)),
+        ] {
+            hasher.update(part.as_bytes());
+            hasher.update(b"\n");
+        }
+        format!("{:x}", hasher.finalize())
+    }
+    
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+    
+    /// A fresh entry for the key. Expired or unreadable entries count as misses and are
+    /// replaced by the next store.
+    pub async fn get(&self, key: &str) -> Option<CachedResponse> {
+        if self.mode == CacheMode::Refresh {
+            return None;
+        }
+        
+        let contents = tokio::fs::read_to_string(self.entry_path(key)).await.ok()?;
+        let entry: CachedResponse = match serde_json::from_str(&contents) {
+            Ok(entry) => entry,
+            Err(e) => {
+                log::warn!("ignoring unreadable response cache entry {}: {}", key, e);
+                return None;
+            }
+        };
+        
+        match self.ttl {
+            Some(ttl) if Utc::now() - entry.stored_at > ttl => None,
+            _ => Some(entry),
+        }
+    }
+    
+    pub async fn put(&self, key: &str, entry: &CachedResponse) -> Result<()> {
+        tokio::fs::create_dir_all(&self.dir).await?;
+        
+        // Write then rename so a concurrent reader never sees a partial file
+        let path = self.entry_path(key);
+        let partial = path.with_extension(format!("{}.tmp", uuid::Uuid::new_v4()));
+        tokio::fs::write(&partial, serde_json::to_string(entry)?).await?;
+        tokio::fs::rename(&partial, &path).await?;
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -1,12 +1,14 @@
 // Complete run command implementation
 use crate::cli::args::RunArgs;
-use crate::core::{evaluation::*, providers::ProviderManager, analysis::AnalysisEngine};
+use crate::core::{evaluation::*, providers::ProviderManager, analysis::AnalysisEngine, cassette::HttpClient, schema::ResponseSchema};
+use crate::core::response_cache::{ResponseCache, CacheMode};
 use crate::utils::error::Result;
 use indicatif::{ProgressBar, ProgressStyle, MultiProgress};
 use futures::stream::{FuturesUnordered, StreamExt};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use console::style;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 pub async fn execute(args: RunArgs) -> Result<()> {
     let storage = crate::core::storage::Storage::new().await?;
@@ -14,20 +16,91 @@ pub async fn execute(args: RunArgs) -> Result<()> {
         .ok_or_else(|| crate::utils::error::EvalError::NotFound(args.name.clone()))?;
     
     // Check if already completed
-    if matches!(evaluation.status, EvaluationStatus::Completed) && !args.force {
+    if matches!(evaluation.status, EvaluationStatus::Completed) && !args.force && !args.resume {
         println!("⚠️  Evaluation '{}' already completed. Use --force to re-run.", args.name);
         return Ok(());
     }
     
+    // Starting over would discard the completions an interrupted run already paid for
+    if matches!(evaluation.status, EvaluationStatus::Running | EvaluationStatus::Cancelled) && !args.force && !args.resume {
+        println!("⚠️  Evaluation '{}' has an interrupted run. Use --resume to continue it or --force to start over.", args.name);
+        return Ok(());
+    }
+    
     println!("🚀 Running evaluation: {}", style(&evaluation.name).cyan().bold());
     
     if let Some(desc) = &evaluation.description {
         println!("📝 {}", desc);
     }
     
-    // Update status
-    evaluation.status = EvaluationStatus::Running;
-    storage.update_evaluation(&evaluation).await?;
+    // A malformed schema would otherwise only surface after every call has been paid for
+    for prompt_config in &evaluation.config.prompts {
+        if let Some(schema) = &prompt_config.response_schema {
+            ResponseSchema::compile(schema)?;
+        }
+        // Only the rendered prompt carries images; inline message data would be dropped silently
+        if prompt_config.messages.iter().any(|m| !m.images.is_empty()) {
+            return Err(crate::utils::error::EvalError::ConfigError(
+                "images can't be set on prompt messages; list them under the prompt's `images` instead".to_string()
+            ));
+        }
+    }
+    
+    // Prompts are fetched and rendered once, so planning, the estimate and execution agree
+    let prompts = resolve_prompts(&evaluation.config).await?;
+    
+    // Resuming continues the latest run and keeps its successful results, trying failed
+    // cells again. Anything else starts a new run alongside the earlier ones.
+    let latest_run = storage.load_run(&evaluation.id, None).await?;
+    let mut crashed_run = None;
+    let (mut run, previous_results) = match latest_run {
+        Some(mut run) if args.resume => {
+            // The kept results only make sense next to new ones executed the same way
+            let changed = config_changes(&run.config, &evaluation.config)?;
+            if !changed.is_empty() {
+                return Err(crate::utils::error::EvalError::ConfigError(format!(
+                    "the {} of '{}' changed since run #{} started; use --force to start a new run with them",
+                    changed.join(", "), evaluation.name, run.number
+                )));
+            }
+            
+            // An edited prompt file or PromptEds prompt renders new cells; results of the old ones are dropped
+            let planned = planned_cells(&prompts, &evaluation.config.providers);
+            let (previous_results, stale): (Vec<ExecutionResult>, Vec<ExecutionResult>) = storage.load_execution_results(&run.id).await?
+                .into_iter()
+                .filter(|r| matches!(r.status, ExecutionStatus::Success))
+                .partition(|r| planned.contains(&(r.cell_id.clone(), r.repetition)));
+            if !stale.is_empty() {
+                println!("⚠️  {} results are for prompts that changed since run #{} started; they are dropped",
+                         style(stale.len()).yellow().bold(), run.number);
+            }
+            // The snapshot records the budget and limits the run finishes with
+            run.config.batch_settings = evaluation.config.batch_settings.clone();
+            run.results = None;
+            run.completed_at = None;
+            (run, previous_results)
+        },
+        None if args.resume => {
+            return Err(crate::utils::error::EvalError::ConfigError(format!(
+                "evaluation '{}' has no run to resume; use --force to start a new run", evaluation.name
+            )));
+        },
+        latest_run => {
+            crashed_run = latest_run.filter(|r| matches!(r.status, EvaluationStatus::Running));
+            let number = storage.next_run_number(&evaluation.id).await?;
+            (EvaluationRun::new(&evaluation, number, RunEnvironment::default()), Vec::new())
+        },
+    };
+    let result_log = ResultLog {
+        storage: &storage,
+        evaluation_id: evaluation.id.clone(),
+        run_id: run.id.clone(),
+        completed: previous_results.iter().map(|r| (r.cell_id.clone(), r.repetition)).collect(),
+        unsaved: std::sync::Mutex::new(HashSet::new()),
+    };
+    if args.resume {
+        println!("♻️  Resuming with {} successful results kept", style(previous_results.len()).green().bold());
+    }
     
     // Calculate total executions
     let total_executions = calculate_total_executions(&evaluation.config);
@@ -38,25 +111,160 @@ pub async fn execute(args: RunArgs) -> Result<()> {
     let overall_progress = multi_progress.add(ProgressBar::new(total_executions as u64));
     overall_progress.set_style(
         ProgressStyle::default_bar()
-            .template("{spinner:.green} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {pos}/{len} executions ({eta})")
+            .template("{spinner:.green} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {pos}/{len} executions ({eta}) {msg}")
             .unwrap()
             .progress_chars("#>-")
     );
     
-    let provider_manager = ProviderManager::new().await?;
-    let mut all_results = Vec::new();
+    let http = match (&args.record, &args.replay) {
+        (Some(dir), _) => {
+            println!("📼 Recording provider traffic to {}", style(dir).cyan());
+            HttpClient::record(dir)?
+        },
+        (_, Some(dir)) => {
+            println!("📼 Replaying provider traffic from {}", style(dir).cyan());
+            HttpClient::replay(dir)?
+        },
+        _ => HttpClient::live(),
+    };
+    
+    let throttle_progress = overall_progress.clone();
+    let mut provider_manager = ProviderManager::with_profile(http, evaluation.config.credential_profile.as_deref()).await?
+        .with_streaming(evaluation.config.batch_settings.streaming)
+        .with_retry_attempts(evaluation.config.batch_settings.retry_attempts)
+        .with_timeout(Duration::from_secs(evaluation.config.batch_settings.timeout_seconds))
+        .with_throttle_notifier(Arc::new(move |provider: &str, wait: Duration| {
+            throttle_progress.set_message(format!("⏳ {} rate limited, waiting {:.1}s", provider, wait.as_secs_f64()));
+        }));
+    
+    // Cassette runs must reach the HTTP layer to record or replay every exchange
+    let config = crate::utils::config::load_config().await?;
+    let cassette = args.record.as_deref().or(args.replay.as_deref());
+    let use_cache = (config.defaults.response_cache || args.refresh_cache) && !args.no_cache;
+    let cache_mode = if use_cache && cassette.is_none() {
+        Some(if args.refresh_cache { CacheMode::Refresh } else { CacheMode::ReadWrite })
+    } else {
+        None
+    };
+    if let Some(mode) = cache_mode {
+        provider_manager = provider_manager.with_cache(
+            ResponseCache::new(ResponseCache::default_dir()?, config.defaults.cache_ttl_hours, mode)
+        );
+    }
+    
+    if let Some(profile) = provider_manager.credential_profile() {
+        println!("🔑 Using credential profile {}", style(profile).cyan());
+    }
+    
+    // Settings a provider can't send would make the comparison uneven without anyone noticing
+    for provider_config in &evaluation.config.providers {
+        let ignored = provider_manager.ignored_settings(&provider_config.name, &provider_config.settings);
+        if !ignored.is_empty() {
+            println!("⚠️  {} does not support {}; these settings are ignored",
+                     provider_config.name, ignored.join(", "));
+        }
+    }
+    
+    // A mis-sized matrix should be caught before anything is paid for
+    let max_cost_usd = args.budget.or(evaluation.config.batch_settings.max_cost_usd);
+    let mut budget = Budget::new(max_cost_usd);
+    // A resumed run already paid for the results it keeps
+    for result in &previous_results {
+        budget.record(result);
+    }
+    let estimate = estimate_run_cost(&evaluation, &prompts, &provider_manager, &result_log).await?;
+    println!("💰 Estimated cost: up to ${:.4} (prompt plus max_tokens of output for every execution)",
+             style(estimate.cost_usd).magenta().bold());
+    if !estimate.unpriced_models.is_empty() {
+        println!("⚠️  No pricing configured for {}; their executions are not included in the estimate or budget",
+                 estimate.unpriced_models.join(", "));
+    }
+    if budget.spent > 0.0 {
+        println!("💰 Already spent on kept results: ${:.4}", budget.spent);
+    }
+    if let Some(limit) = max_cost_usd {
+        if budget.spent + estimate.cost_usd > limit {
+            if !args.yes {
+                let message = if budget.spent > 0.0 {
+                    format!("estimated cost ${:.4} plus ${:.4} already spent exceeds the ${:.4} budget",
+                            estimate.cost_usd, budget.spent, limit)
+                } else {
+                    format!("estimated cost ${:.4} exceeds the ${:.4} budget", estimate.cost_usd, limit)
+                };
+                return Err(crate::utils::error::EvalError::BudgetExceeded(message));
+            }
+            println!("⚠️  Estimated cost exceeds the ${:.4} budget; the run stops once it is spent", limit);
+        }
+    }
+    
+    // A crashed run is never going to finish; it stays in the history as cancelled
+    if let Some(crashed_run) = &mut crashed_run {
+        crashed_run.status = EvaluationStatus::Cancelled;
+        storage.save_run(crashed_run).await?;
+    }
+    
+    // Failed and dropped cells are executed again, and their new results take the old ones' place
+    if args.resume {
+        let kept: Vec<String> = previous_results.iter().map(|r| r.id.clone()).collect();
+        storage.retain_results(&run.id, &kept).await?;
+    }
+    
+    // Update status
+    run.status = EvaluationStatus::Running;
+    run.environment = RunEnvironment::current(
+        provider_manager.credential_profile(),
+        cassette,
+        cache_mode == Some(CacheMode::ReadWrite),
+    );
+    storage.save_run(&run).await?;
+    evaluation.status = EvaluationStatus::Running;
+    storage.update_evaluation(&evaluation).await?;
+    
+    let mut all_results = previous_results;
+    let mut control = RunControl {
+        budget,
+        cancellation: Cancellation::listen(overall_progress.clone()),
+        not_started: 0,
+    };
     
     // Execute evaluations
     let start_time = Instant::now();
     
     if evaluation.config.batch_settings.parallel_execution {
-        all_results = execute_parallel(&evaluation, &provider_manager, &overall_progress, args.max_concurrent).await?;
+        all_results.extend(execute_parallel(&evaluation, &prompts, &provider_manager, &overall_progress, args.max_concurrent, &mut control, &result_log).await?);
     } else {
-        all_results = execute_sequential(&evaluation, &provider_manager, &overall_progress).await?;
+        all_results.extend(execute_sequential(&evaluation, &prompts, &provider_manager, &overall_progress, &mut control, &result_log).await?);
     }
     
     let execution_time = start_time.elapsed();
-    overall_progress.finish_with_message("✅ All executions completed");
+    control.cancellation.stop_listening();
+    let cancelled = control.cancellation.is_cancelled();
+    if cancelled {
+        overall_progress.abandon_with_message("⏹️ Cancelled");
+        println!("⏹️  Run cancelled; {} executions did not finish", style(control.not_started).yellow().bold());
+    } else if control.not_started > 0 {
+        overall_progress.abandon_with_message("💸 Budget reached");
+        println!("💸 Budget of ${:.4} reached after ${:.4} spent; {} executions were not started",
+                 max_cost_usd.unwrap_or_default(), control.budget.spent, style(control.not_started).yellow().bold());
+    } else {
+        overall_progress.finish_with_message("✅ All executions completed");
+    }
+    
+    // A replay with unmatched requests would silently produce a partial evaluation
+    let replay_misses = provider_manager.http().replay_misses().await;
+    if !replay_misses.is_empty() {
+        for request in &replay_misses {
+            eprintln!("❌ No recorded response: {}", request);
+        }
+        result_log.save_unsaved(&all_results).await?;
+        run.status = EvaluationStatus::Failed;
+        storage.save_run(&run).await?;
+        evaluation.status = EvaluationStatus::Failed;
+        storage.update_evaluation(&evaluation).await?;
+        return Err(crate::utils::error::EvalError::ReplayError(format!(
+            "{} request(s) had no recorded match in the cassette", replay_misses.len()
+        )));
+    }
     
     println!("⏱️  Total execution time: {:.2}s", execution_time.as_secs_f64());
     
@@ -75,57 +283,334 @@ pub async fn execute(args: RunArgs) -> Result<()> {
     let analysis = analysis_engine.analyze_results(&all_results, &evaluation.config.analysis_options).await?;
     
     analysis_progress.set_message("Generating summary...");
-    let summary = generate_summary(&all_results, &analysis).await?;
+    let summary = generate_summary(&all_results, &analysis, control.not_started).await?;
     
     analysis_progress.finish_with_message("✅ Analysis completed");
     
+    // A $0 total is misleading when some models simply have no prices configured
+    if !analysis.cost_breakdown.unpriced_models.is_empty() {
+        println!("⚠️  No pricing configured for {}; cost marked unknown",
+                 analysis.cost_breakdown.unpriced_models.join(", "));
+    }
+    
+    let estimated_models = models_with_estimated_tokens(&all_results);
+    if !estimated_models.is_empty() {
+        println!("ℹ️  Token counts for {} are estimated from text length; configure a tokenizer for exact counts",
+                 estimated_models.join(", "));
+    }
+    
     // Store results
-    evaluation.results = Some(EvaluationResults {
+    result_log.save_unsaved(&all_results).await?;
+    run.results = Some(EvaluationResults {
         executions: all_results,
         analysis,
         summary,
         report_path: None,
     });
-    evaluation.status = EvaluationStatus::Completed;
-    evaluation.completed_at = Some(chrono::Utc::now());
+    // A cancelled run keeps what finished and can be picked up again with --resume
+    if cancelled {
+        run.status = EvaluationStatus::Cancelled;
+    } else {
+        run.status = EvaluationStatus::Completed;
+        run.completed_at = Some(chrono::Utc::now());
+    }
+    storage.save_run(&run).await?;
     
+    // The evaluation mirrors its latest run; storage reads its results back from the run
+    evaluation.results = run.results.clone();
+    evaluation.status = run.status.clone();
+    evaluation.completed_at = run.completed_at;
     storage.update_evaluation(&evaluation).await?;
     
+    if config.defaults.runs_kept > 0 {
+        let pruned = storage.prune_runs(&evaluation.id, config.defaults.runs_kept).await?;
+        if pruned > 0 {
+            println!("🗑️  Dropped {} old run(s); runs_kept is {}", pruned, config.defaults.runs_kept);
+        }
+    }
+    
     // Display summary
     display_execution_summary(&evaluation)?;
     
-    println!("\n✅ Evaluation '{}' completed successfully", style(&evaluation.name).green().bold());
+    if cancelled {
+        return Err(crate::utils::error::EvalError::Cancelled(format!(
+            "evaluation '{}' stopped with {} executions unfinished", evaluation.name, control.not_started
+        )));
+    }
+    
+    println!("\n✅ Evaluation '{}' completed successfully as run #{}", style(&evaluation.name).green().bold(), run.number);
+    println!("📚 Compare runs: {}", style(format!("evaleds runs {}", evaluation.name)).cyan());
     println!("📊 View results: {}", style(format!("evaleds view {}", evaluation.name)).cyan());
     
     Ok(())
 }
 
+/// How long in-flight requests get to finish once a run is cancelled
+const CANCEL_GRACE_PERIOD: Duration = Duration::from_secs(30);
+
+/// What stops a run before the whole matrix has been executed
+struct RunControl {
+    budget: Budget,
+    cancellation: Cancellation,
+    not_started: u32,                         // Executions skipped or abandoned once the run stopped
+}
+
+impl RunControl {
+    /// Nothing new is dispatched once the budget is spent or the run is cancelled
+    fn should_stop(&self) -> bool {
+        self.budget.exhausted() || self.cancellation.is_cancelled()
+    }
+}
+
+/// Set by the first SIGINT or SIGTERM. A second signal exits immediately, for when
+/// waiting on in-flight requests takes longer than the user will.
+#[derive(Clone)]
+struct Cancellation {
+    receiver: tokio::sync::watch::Receiver<bool>,
+    listener: Arc<tokio::task::AbortHandle>,
+}
+
+impl Cancellation {
+    fn listen(progress: ProgressBar) -> Self {
+        let (sender, receiver) = tokio::sync::watch::channel(false);
+        let listener = tokio::spawn(async move {
+            wait_for_signal().await;
+            let _ = sender.send(true);
+            progress.set_message(format!(
+                "⏹️ Cancelling: waiting up to {}s for in-flight requests (signal again to quit now)",
+                CANCEL_GRACE_PERIOD.as_secs()
+            ));
+            
+            wait_for_signal().await;
+            progress.abandon();
+            std::process::exit(130);
+        });
+        Self { receiver, listener: Arc::new(listener.abort_handle()) }
+    }
+    
+    /// Stop listening once execution is over. Signals during the analysis and save that
+    /// follow are then ignored, so the results already paid for are always stored.
+    fn stop_listening(&self) {
+        self.listener.abort();
+    }
+    
+    fn is_cancelled(&self) -> bool {
+        *self.receiver.borrow()
+    }
+    
+    /// Resolves once the run is cancelled; never, if it isn't
+    async fn cancelled(&self) {
+        let mut receiver = self.receiver.clone();
+        if receiver.wait_for(|cancelled| *cancelled).await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
+async fn wait_for_signal() {
+    // A handler that can't be installed just never fires
+    let interrupt = async {
+        if tokio::signal::ctrl_c().await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    };
+    
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => { signal.recv().await; },
+            Err(_) => std::future::pending::<()>().await,
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+    
+    tokio::select! {
+        _ = interrupt => {},
+        _ = terminate => {},
+    }
+}
+
+/// (cell, repetition) pairs the run executes with its prompts as they rendered at the start
+fn planned_cells(prompts: &[RunPrompt], providers: &[ProviderConfig]) -> HashSet<(String, u32)> {
+    let mut planned = HashSet::new();
+    
+    for prompt in prompts {
+        for resolved_prompt in &prompt.variations {
+            for provider_config in providers {
+                for model in &provider_config.models {
+                    let cell = resolved_prompt.cell_id(&provider_config.name, model);
+                    planned.extend((0..prompt.repetitions).map(|repetition| (cell.clone(), repetition)));
+                }
+            }
+        }
+    }
+    
+    planned
+}
+
+/// Parts of an evaluation's config that differ from the snapshot a run started with
+fn config_changes(run_config: &EvaluationConfig, current: &EvaluationConfig) -> Result<Vec<&'static str>> {
+    let mut changed = Vec::new();
+    if serde_json::to_value(&run_config.prompts)? != serde_json::to_value(&current.prompts)? {
+        changed.push("prompts");
+    }
+    if serde_json::to_value(&run_config.providers)? != serde_json::to_value(&current.providers)? {
+        changed.push("providers");
+    }
+    if serde_json::to_value(&run_config.analysis_options)? != serde_json::to_value(&current.analysis_options)? {
+        changed.push("analysis options");
+    }
+    // Budget, concurrency, timeout and retries only change how a run executes, not what a cell
+    // produces, so they can be adjusted before resuming, e.g. after the budget stopped a run
+    if run_config.batch_settings.repetitions != current.batch_settings.repetitions {
+        changed.push("repetitions");
+    }
+    if run_config.batch_settings.streaming != current.batch_settings.streaming {
+        changed.push("streaming setting");
+    }
+    if run_config.credential_profile != current.credential_profile {
+        changed.push("credential profile");
+    }
+    Ok(changed)
+}
+
+/// Spend against the run's cost budget. Cache hits were not paid for and don't count.
+struct Budget {
+    limit: Option<f64>,
+    spent: f64,
+}
+
+impl Budget {
+    fn new(limit: Option<f64>) -> Self {
+        Self { limit, spent: 0.0 }
+    }
+    
+    fn record(&mut self, result: &ExecutionResult) {
+        if !result.metadata.cache_hit {
+            self.spent += result.metadata.cost_usd;
+        }
+    }
+    
+    fn exhausted(&self) -> bool {
+        self.limit.is_some_and(|limit| self.spent >= limit)
+    }
+}
+
+/// Results are written to storage as they complete, so an interrupted run can be resumed
+struct ResultLog<'a> {
+    storage: &'a crate::core::storage::Storage,
+    evaluation_id: String,
+    run_id: String,
+    completed: HashSet<(String, u32)>,       // (cell, repetition) pairs that already succeeded
+    unsaved: std::sync::Mutex<HashSet<String>>,  // Ids of results whose write failed
+}
+
+impl ResultLog<'_> {
+    fn is_done(&self, provider: &str, model: &str, prompt: &RenderedPrompt, repetition: u32) -> bool {
+        !self.completed.is_empty() && self.completed.contains(&(prompt.cell_id(provider, model), repetition))
+    }
+    
+    /// A failed write doesn't stop the run; the result is kept in memory and written again by `save_unsaved`
+    async fn persist(&self, result: &ExecutionResult) {
+        if let Err(e) = self.storage.save_execution_result(&self.evaluation_id, &self.run_id, result).await {
+            eprintln!("⚠️  Could not save result for {}/{}: {}", result.provider, result.model, e);
+            self.unsaved.lock().unwrap().insert(result.id.clone());
+        }
+    }
+    
+    /// Stored executions are the run's only copy of its results, so writes that failed mid-run
+    /// get another try before the run is saved
+    async fn save_unsaved(&self, results: &[ExecutionResult]) -> Result<()> {
+        let unsaved = std::mem::take(&mut *self.unsaved.lock().unwrap());
+        for result in results.iter().filter(|r| unsaved.contains(&r.id)) {
+            self.storage.save_execution_result(&self.evaluation_id, &self.run_id, result).await?;
+        }
+        Ok(())
+    }
+}
+
+struct RunEstimate {
+    cost_usd: f64,
+    unpriced_models: Vec<String>,
+}
+
+/// Upper bound on the cost of the full prompt x provider x model matrix, before cache hits
+async fn estimate_run_cost(evaluation: &Evaluation, prompts: &[RunPrompt], provider_manager: &ProviderManager, result_log: &ResultLog<'_>) -> Result<RunEstimate> {
+    let mut cost_usd = 0.0;
+    let mut unpriced_models = Vec::new();
+    
+    for prompt in prompts {
+        let repetitions = prompt.repetitions;
+        
+        for resolved_prompt in &prompt.variations {
+            for provider_config in &evaluation.config.providers {
+                for model in &provider_config.models {
+                    let remaining = (0..repetitions)
+                        .filter(|repetition| !result_log.is_done(&provider_config.name, model, resolved_prompt, *repetition))
+                        .count();
+                    if remaining == 0 {
+                        continue;
+                    }
+                    
+                    match provider_manager.estimate_cost(&provider_config.name, model, &resolved_prompt.messages, &provider_config.settings) {
+                        Some(cost) => cost_usd += cost * remaining as f64,
+                        None => {
+                            let model_key = format!("{}/{}", provider_config.name, model);
+                            if !unpriced_models.contains(&model_key) {
+                                unpriced_models.push(model_key);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    
+    unpriced_models.sort();
+    Ok(RunEstimate { cost_usd, unpriced_models })
+}
+
 async fn execute_parallel(
     evaluation: &Evaluation,
+    prompts: &[RunPrompt],
     provider_manager: &ProviderManager,
     progress: &ProgressBar,
     max_concurrent: Option<u32>,
+    control: &mut RunControl,
+    result_log: &ResultLog<'_>,
 ) -> Result<Vec<ExecutionResult>> {
+    // Provider rate limits throttle requests, so concurrency is only bounded by the setting
     let max_concurrent = max_concurrent
         .unwrap_or(evaluation.config.batch_settings.max_concurrent)
-        .min(20); // Safety limit
+        .max(1);
     
     let mut futures = FuturesUnordered::new();
     let mut results = Vec::new();
     let mut pending_executions = Vec::new();
     
     // Prepare all executions
-    for prompt_config in &evaluation.config.prompts {
-        let resolved_prompts = resolve_prompt_config(prompt_config).await?;
+    for prompt in prompts {
+        let (prompt_config, repetitions) = (&prompt.config, prompt.repetitions);
         
-        for resolved_prompt in resolved_prompts {
+        for resolved_prompt in &prompt.variations {
             for provider_config in &evaluation.config.providers {
                 for model in &provider_config.models {
-                    pending_executions.push((
-                        resolved_prompt.clone(),
-                        provider_config.clone(),
-                        model.clone(),
-                    ));
+                    for repetition in 0..repetitions {
+                        if result_log.is_done(&provider_config.name, model, &resolved_prompt, repetition) {
+                            progress.inc(1);
+                            continue;
+                        }
+                        
+                        pending_executions.push((
+                            resolved_prompt.clone(),
+                            prompt_config,
+                            provider_config.clone(),
+                            model.clone(),
+                            repetition,
+                            repetitions,
+                        ));
+                    }
                 }
             }
         }
@@ -137,13 +622,16 @@ async fn execute_parallel(
     
     // Start initial batch
     while executing < max_concurrent {
-        if let Some((prompt, provider_config, model)) = pending_iter.next() {
+        if let Some((prompt, prompt_config, provider_config, model, repetition, repetitions)) = pending_iter.next() {
             let future = execute_single_prompt(
                 provider_manager,
                 &provider_config.name,
                 &model,
                 &prompt,
+                prompt_config,
                 &provider_config.settings,
+                repetition,
+                repetitions,
             );
             futures.push(future);
             executing += 1;
@@ -153,24 +641,59 @@ async fn execute_parallel(
     }
     
     // Process results and start new executions
+    let cancellation = control.cancellation.clone();
+    let mut deadline = None;
     while !futures.is_empty() {
-        if let Some(result) = futures.next().await {
+        let next = match deadline {
+            Some(deadline) => match tokio::time::timeout_at(deadline, futures.next()).await {
+                Ok(next) => next,
+                Err(_) => {
+                    // Abandoned requests are retried by --resume
+                    control.not_started += futures.len() as u32;
+                    break;
+                }
+            },
+            None => tokio::select! {
+                next = futures.next() => next,
+                _ = cancellation.cancelled() => {
+                    control.not_started += pending_iter.by_ref().count() as u32;
+                    deadline = Some(tokio::time::Instant::now() + CANCEL_GRACE_PERIOD);
+                    continue;
+                }
+            },
+        };
+        
+        if let Some(result) = next {
             match result {
-                Ok(execution_result) => results.push(execution_result),
+                Ok(execution_result) => {
+                    control.budget.record(&execution_result);
+                    result_log.persist(&execution_result).await;
+                    results.push(execution_result);
+                },
                 Err(e) => eprintln!("❌ Execution failed: {}", e),
             }
             
             progress.inc(1);
+            progress.set_message("");
             executing -= 1;
             
+            // In-flight executions finish; nothing new starts once the run is stopping
+            if control.should_stop() {
+                control.not_started += pending_iter.by_ref().count() as u32;
+                continue;
+            }
+            
             // Start next execution if available
-            if let Some((prompt, provider_config, model)) = pending_iter.next() {
+            if let Some((prompt, prompt_config, provider_config, model, repetition, repetitions)) = pending_iter.next() {
                 let future = execute_single_prompt(
                     provider_manager,
                     &provider_config.name,
                     &model,
                     &prompt,
+                    prompt_config,
                     &provider_config.settings,
+                    repetition,
+                    repetitions,
                 );
                 futures.push(future);
                 executing += 1;
@@ -183,32 +706,71 @@ async fn execute_parallel(
 
 async fn execute_sequential(
     evaluation: &Evaluation,
+    prompts: &[RunPrompt],
     provider_manager: &ProviderManager,
     progress: &ProgressBar,
+    control: &mut RunControl,
+    result_log: &ResultLog<'_>,
 ) -> Result<Vec<ExecutionResult>> {
     let mut results = Vec::new();
+    let cancellation = control.cancellation.clone();
     
-    for prompt_config in &evaluation.config.prompts {
-        let resolved_prompts = resolve_prompt_config(prompt_config).await?;
+    for prompt in prompts {
+        let (prompt_config, repetitions) = (&prompt.config, prompt.repetitions);
         
-        for resolved_prompt in resolved_prompts {
+        for resolved_prompt in &prompt.variations {
             for provider_config in &evaluation.config.providers {
                 for model in &provider_config.models {
-                    match execute_single_prompt(
-                        provider_manager,
-                        &provider_config.name,
-                        model,
-                        &resolved_prompt,
-                        &provider_config.settings,
-                    ).await {
-                        Ok(result) => results.push(result),
-                        Err(e) => eprintln!("❌ Failed {}/{}: {}", provider_config.name, model, e),
+                    for repetition in 0..repetitions {
+                        if result_log.is_done(&provider_config.name, model, &resolved_prompt, repetition) {
+                            progress.inc(1);
+                            continue;
+                        }
+                        
+                        if control.should_stop() {
+                            control.not_started += 1;
+                            continue;
+                        }
+                        
+                        let execution = execute_single_prompt(
+                            provider_manager,
+                            &provider_config.name,
+                            model,
+                            &resolved_prompt,
+                            prompt_config,
+                            &provider_config.settings,
+                            repetition,
+                            repetitions,
+                        );
+                        tokio::pin!(execution);
+                        
+                        // A request in flight when the run is cancelled gets the grace period to finish
+                        let outcome = tokio::select! {
+                            outcome = &mut execution => Some(outcome),
+                            _ = cancellation.cancelled() => {
+                                tokio::time::timeout(CANCEL_GRACE_PERIOD, &mut execution).await.ok()
+                            }
+                        };
+                        
+                        match outcome {
+                            Some(Ok(result)) => {
+                                control.budget.record(&result);
+                                result_log.persist(&result).await;
+                                results.push(result);
+                            },
+                            Some(Err(e)) => eprintln!("❌ Failed {}/{}: {}", provider_config.name, model, e),
+                            None => {
+                                control.not_started += 1;
+                                continue;
+                            }
+                        }
+                        
+                        progress.inc(1);
+                        progress.set_message("");
+                        
+                        // Small delay to be respectful to APIs
+                        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
                     }
-                    
-                    progress.inc(1);
-                    
-                    // Small delay to be respectful to APIs
-                    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
                 }
             }
         }
@@ -221,13 +783,82 @@ async fn execute_single_prompt(
     provider_manager: &ProviderManager,
     provider_name: &str,
     model: &str,
-    prompt: &str,
+    prompt: &RenderedPrompt,
+    prompt_config: &PromptConfig,
     settings: &ModelSettings,
+    repetition: u32,
+    repetitions: u32,
 ) -> Result<ExecutionResult> {
-    provider_manager.execute_prompt(provider_name, model, prompt, settings).await
+    let mut result = provider_manager.execute_prompt(
+        provider_name,
+        model,
+        &prompt.messages,
+        &prompt_config.tools,
+        prompt_config.response_schema.as_ref(),
+        settings,
+        repetition,
+        repetitions,
+    ).await?;
+    result.expected_tool_calls = prompt_config.expected_tool_calls.clone();
+    result.response_schema = prompt_config.response_schema.clone();
+    result.cell_id = prompt.cell_id(provider_name, model);
+    result.resolved_prompt = Some(prompt.record.clone());
+    Ok(result)
+}
+
+fn prompt_repetitions(prompt_config: &PromptConfig, batch_settings: &BatchSettings) -> u32 {
+    prompt_config.repetitions.unwrap_or(batch_settings.repetitions).max(1)
+}
+
+/// A prompt config rendered for the run, with the number of samples per cell
+struct RunPrompt {
+    config: PromptConfig,
+    repetitions: u32,
+    variations: Vec<RenderedPrompt>,          // Base variables first, then each variation
+}
+
+/// Fetch and render every prompt of the evaluation. PromptEds prompts and images are read here only.
+async fn resolve_prompts(config: &EvaluationConfig) -> Result<Vec<RunPrompt>> {
+    let mut prompts = Vec::new();
+    for (prompt_index, prompt_config) in config.prompts.iter().enumerate() {
+        prompts.push(RunPrompt {
+            config: prompt_config.clone(),
+            repetitions: prompt_repetitions(prompt_config, &config.batch_settings),
+            variations: resolve_prompt_config(prompt_index, prompt_config).await?,
+        });
+    }
+    Ok(prompts)
+}
+
+/// A prompt variation ready to send, with the record of where it came from
+#[derive(Clone)]
+struct RenderedPrompt {
+    record: ResolvedPrompt,
+    messages: Vec<ChatMessage>,
+}
+
+impl RenderedPrompt {
+    /// `variation` is the variation's position and name; None for the base variables
+    fn new(prompt_index: usize, variation: Option<(usize, &str)>, variables: &HashMap<String, String>, messages: Vec<ChatMessage>) -> Self {
+        let record = ResolvedPrompt {
+            prompt_index: prompt_index as u32,
+            variation: variation.map(|(index, _)| index as u32),
+            variation_name: variation.map(|(_, name)| name.to_string()),
+            variables: variables.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+            content_hash: prompt_hash(&messages),
+        };
+        Self { record, messages }
+    }
+    
+    fn cell_id(&self, provider: &str, model: &str) -> String {
+        cell_id(provider, model, self.record.variation_name.as_deref(), &self.messages)
+    }
 }
 
-async fn resolve_prompt_config(prompt_config: &PromptConfig) -> Result<Vec<String>> {
+/// Resolve a prompt config into one rendered prompt per variation, the base variables first.
+/// Configured messages (system prompt, prior turns) come first, followed by the
+/// rendered prompt as the final user turn, which carries any attached images.
+async fn resolve_prompt_config(prompt_index: usize, prompt_config: &PromptConfig) -> Result<Vec<RenderedPrompt>> {
     let mut resolved_prompts = Vec::new();
     
     // Get base prompt content
@@ -243,34 +874,53 @@ async fn resolve_prompt_config(prompt_config: &PromptConfig) -> Result<Vec<Strin
         },
     };
     
-    // Apply base variables
-    let mut rendered_content = base_content;
-    for (key, value) in &prompt_config.variables {
-        rendered_content = rendered_content.replace(&format!("{{{{{}}}}}", key), value);
-    }
+    // Images are read once and shared by every variation
+    let images = load_images(&prompt_config.images).await?;
     
-    resolved_prompts.push(rendered_content.clone());
+    // Apply base variables
+    let messages = render_messages(prompt_config, &base_content, &prompt_config.variables, &images);
+    resolved_prompts.push(RenderedPrompt::new(prompt_index, None, &prompt_config.variables, messages));
     
     // Add variations
-    for variation in &prompt_config.variations {
-        let mut variation_content = base_content.clone();
-        for (key, value) in &variation.variables {
-            variation_content = variation_content.replace(&format!("{{{{{}}}}}", key), value);
-        }
-        resolved_prompts.push(variation_content);
+    for (variation_index, variation) in prompt_config.variations.iter().enumerate() {
+        let messages = render_messages(prompt_config, &base_content, &variation.variables, &images);
+        resolved_prompts.push(RenderedPrompt::new(prompt_index, Some((variation_index, &variation.name)), &variation.variables, messages));
     }
     
     Ok(resolved_prompts)
 }
 
+fn render_messages(
+    prompt_config: &PromptConfig,
+    content: &str,
+    variables: &HashMap<String, String>,
+    images: &[ImageData],
+) -> Vec<ChatMessage> {
+    let mut messages: Vec<ChatMessage> = prompt_config.messages.iter()
+        .map(|m| ChatMessage::new(m.role, render_variables(&m.content, variables)))
+        .collect();
+    
+    messages.push(ChatMessage::user(render_variables(content, variables)).with_images(images.to_vec()));
+    messages
+}
+
+fn render_variables(template: &str, variables: &HashMap<String, String>) -> String {
+    let mut rendered = template.to_string();
+    for (key, value) in variables {
+        rendered = rendered.replace(&format!("{{{{{}}}}}", key), value);
+    }
+    rendered
+}
+
 fn calculate_total_executions(config: &EvaluationConfig) -> u32 {
     let mut total = 0;
     
     for prompt_config in &config.prompts {
         let prompt_variations = 1 + prompt_config.variations.len() as u32;
+        let repetitions = prompt_repetitions(prompt_config, &config.batch_settings);
         
         for provider_config in &config.providers {
-            total += prompt_variations * provider_config.models.len() as u32;
+            total += prompt_variations * repetitions * provider_config.models.len() as u32;
         }
     }
     
@@ -280,14 +930,20 @@ fn calculate_total_executions(config: &EvaluationConfig) -> u32 {
 async fn generate_summary(
     results: &[ExecutionResult],
     analysis: &AnalysisResults,
+    skipped_executions: u32,
 ) -> Result<ResultSummary> {
     let total_executions = results.len() as u32;
     let successful_executions = results.iter()
         .filter(|r| matches!(r.status, ExecutionStatus::Success))
         .count() as u32;
-    let failed_executions = total_executions - successful_executions;
+    let timed_out_executions = results.iter()
+        .filter(|r| matches!(r.status, ExecutionStatus::Timeout))
+        .count() as u32;
+    let failed_executions = total_executions - successful_executions - timed_out_executions;
     
+    // Cache hits were not paid for by this run
     let total_cost = results.iter()
+        .filter(|r| !r.metadata.cache_hit)
         .map(|r| r.metadata.cost_usd)
         .sum::<f64>();
     
@@ -314,6 +970,8 @@ async fn generate_summary(
         total_executions,
         successful_executions,
         failed_executions,
+        timed_out_executions,
+        skipped_executions,
         total_cost,
         avg_response_time,
         success_rate,
@@ -323,6 +981,17 @@ async fn generate_summary(
     })
 }
 
+/// Models whose provider reported no usage and that have no tokenizer configured
+fn models_with_estimated_tokens(results: &[ExecutionResult]) -> Vec<String> {
+    let mut models: Vec<String> = results.iter()
+        .filter(|r| r.metadata.token_count_source == TokenCountSource::Estimate)
+        .map(|r| format!("{}/{}", r.provider, r.model))
+        .collect();
+    models.sort();
+    models.dedup();
+    models
+}
+
 fn find_best_performing_model(results: &[ExecutionResult]) -> Option<String> {
     // Simple heuristic: lowest average response time with high success rate
     let mut model_stats: HashMap<String, (f64, u32, u32)> = HashMap::new();
@@ -351,7 +1020,8 @@ fn find_most_cost_effective_model(results: &[ExecutionResult]) -> Option<String>
     let mut model_costs: HashMap<String, f64> = HashMap::new();
     
     for result in results {
-        if matches!(result.status, ExecutionStatus::Success) {
+        // Unpriced models report $0 and would otherwise always win
+        if matches!(result.status, ExecutionStatus::Success) && !result.metadata.cost_unknown {
             let model_key = format!("{}/{}", result.provider, result.model);
             *model_costs.entry(model_key).or_insert(0.0) += result.metadata.cost_usd;
         }
@@ -387,8 +1057,26 @@ fn display_execution_summary(evaluation: &Evaluation) -> Result<()> {
         println!("  Total: {}", style(results.summary.total_executions).yellow().bold());
         println!("  Success: {}", style(results.summary.successful_executions).green().bold());
         println!("  Failed: {}", style(results.summary.failed_executions).red().bold());
+        if results.summary.timed_out_executions > 0 {
+            println!("  Timed Out: {}", style(results.summary.timed_out_executions).yellow().bold());
+        }
+        if results.summary.skipped_executions > 0 {
+            println!("  Not Finished (budget or cancel): {}", style(results.summary.skipped_executions).yellow().bold());
+        }
         println!("  Success Rate: {:.1}%", style(results.summary.success_rate).cyan().bold());
         println!("  Total Cost: ${:.4}", style(results.summary.total_cost).magenta().bold());
+        let cache_savings = results.analysis.cost_breakdown.cache_savings;
+        let cache_hits = results.executions.iter().filter(|r| r.metadata.cache_hit).count();
+        if cache_hits > 0 {
+            println!("  Response Cache: {} hits, ${:.4} saved",
+                     style(cache_hits).cyan().bold(),
+                     cache_savings);
+        }
+        // A run served mostly from the cache repeats earlier measurements instead of taking new ones
+        if cache_hits * 2 > results.executions.len() {
+            println!("⚠️  {} of {} results served from cache; use --refresh-cache to measure drift",
+                     cache_hits, results.executions.len());
+        }
         println!("  Avg Response Time: {:.0}ms", style(results.summary.avg_response_time).blue().bold());
         
         if let Some(best) = &results.summary.best_performing_model {
@@ -400,7 +1088,136 @@ fn display_execution_summary(evaluation: &Evaluation) -> Result<()> {
         if let Some(fastest) = &results.summary.fastest_model {
             println!("  Fastest: {}", style(fastest).green().bold());
         }
+        
+        if !results.analysis.tool_call_scores.is_empty() {
+            println!("\n🛠️  Tool Calling:");
+            for score in &results.analysis.tool_call_scores {
+                println!("  {}/{}: selection {:.0}%, arguments {:.0}%, exact {:.0}%",
+                         score.provider, score.model,
+                         score.tool_selection_accuracy * 100.0,
+                         score.argument_accuracy * 100.0,
+                         score.exact_match_rate * 100.0);
+            }
+        }
+        
+        if !results.analysis.cell_variance.is_empty() {
+            println!("\n🔁 Consistency Across Repetitions:");
+            let mut by_model: Vec<(String, Vec<&CellVariance>)> = Vec::new();
+            for cell in &results.analysis.cell_variance {
+                let key = format!("{}/{}", cell.provider, cell.model);
+                match by_model.iter_mut().find(|(model, _)| *model == key) {
+                    Some((_, cells)) => cells.push(cell),
+                    None => by_model.push((key, vec![cell])),
+                }
+            }
+            
+            for (model, cells) in by_model {
+                let agreements: Vec<f32> = cells.iter().filter_map(|c| c.self_agreement).collect();
+                let latency_sd: Vec<f64> = cells.iter().filter_map(|c| c.latency_ms.as_ref().map(|l| l.std_dev)).collect();
+                let agreement = if agreements.is_empty() {
+                    "-".to_string()
+                } else {
+                    format!("{:.2}", agreements.iter().sum::<f32>() / agreements.len() as f32)
+                };
+                let latency = if latency_sd.is_empty() {
+                    "-".to_string()
+                } else {
+                    format!("±{:.0}ms", latency_sd.iter().sum::<f64>() / latency_sd.len() as f64)
+                };
+                println!("  {}: self-agreement {}, latency {} across {} cells", model, agreement, latency, cells.len());
+            }
+        }
+        
+        if !results.analysis.schema_conformance_by_model.is_empty() {
+            println!("\n🧩 Schema Conformance:");
+            for rate in &results.analysis.schema_conformance_by_model {
+                println!("  {}/{}: parses {:.0}%, valid {:.0}%",
+                         rate.provider, rate.model, rate.parse_rate, rate.valid_rate);
+                if !rate.failed_paths.is_empty() {
+                    println!("    Failed paths: {}", rate.failed_paths.iter().take(5).cloned().collect::<Vec<_>>().join(", "));
+                }
+            }
+        }
     }
     
     Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn file_config(path: &std::path::Path) -> EvaluationConfig {
+        EvaluationConfig {
+            prompts: vec![PromptConfig {
+                source: PromptSource::File { path: path.to_string_lossy().to_string() },
+                variables: HashMap::new(),
+                variations: Vec::new(),
+                messages: Vec::new(),
+                tools: Vec::new(),
+                expected_tool_calls: Vec::new(),
+                response_schema: None,
+                images: Vec::new(),
+                repetitions: Some(2),
+            }],
+            providers: vec![ProviderConfig {
+                name: "openai".to_string(),
+                models: vec!["gpt-4".to_string(), "gpt-3.5-turbo".to_string()],
+                settings: ModelSettings::default(),
+            }],
+            analysis_options: AnalysisOptions {
+                response_metrics: true,
+                similarity_analysis: false,
+                content_analysis: false,
+                quality_indicators: false,
+                cost_analysis: true,
+                performance_analysis: false,
+            },
+            batch_settings: BatchSettings::default(),
+            credential_profile: None,
+        }
+    }
+    
+    #[tokio::test]
+    async fn planned_cells_cover_every_model_and_repetition() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("prompt.txt");
+        std::fs::write(&path, "Summarize the report").unwrap();
+        
+        let config = file_config(&path);
+        let planned = planned_cells(&resolve_prompts(&config).await.unwrap(), &config.providers);
+        assert_eq!(planned.len(), 4);
+        assert!(planned.iter().all(|(_, repetition)| *repetition < 2));
+    }
+    
+    // The config is unchanged, so only the rendered cells tell a resumed run its kept results are stale
+    #[tokio::test]
+    async fn changed_prompt_file_plans_new_cells() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("prompt.txt");
+        let config = file_config(&path);
+        
+        std::fs::write(&path, "Summarize the report").unwrap();
+        let before = planned_cells(&resolve_prompts(&config).await.unwrap(), &config.providers);
+        std::fs::write(&path, "Summarize the report in one sentence").unwrap();
+        let after = planned_cells(&resolve_prompts(&config).await.unwrap(), &config.providers);
+        
+        assert!(config_changes(&config, &config).unwrap().is_empty());
+        assert!(before.is_disjoint(&after));
+        assert_eq!(planned_cells(&resolve_prompts(&config).await.unwrap(), &config.providers), after);
+    }
+    
+    #[test]
+    fn raised_budget_does_not_block_resume() {
+        let run_config = file_config(std::path::Path::new("prompt.txt"));
+        let mut current = run_config.clone();
+        current.batch_settings.max_cost_usd = Some(10.0);
+        current.batch_settings.max_concurrent = 1;
+        current.batch_settings.timeout_seconds = 300;
+        current.batch_settings.retry_attempts = 5;
+        assert!(config_changes(&run_config, &current).unwrap().is_empty());
+        
+        current.batch_settings.repetitions = 3;
+        assert_eq!(config_changes(&run_config, &current).unwrap(), vec!["repetitions"]);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,164 @@
+// JSON Schema conformance checks for structured-output prompts
+use crate::core::evaluation::SchemaViolation;
+use crate::utils::error::{Result, EvalError};
+use jsonschema::JSONSchema;
+
+/// A prompt's response schema, compiled once and checked against each output
+pub struct ResponseSchema {
+    compiled: JSONSchema,
+}
+
+/// Outcome of checking one output
+#[derive(Debug, Clone)]
+pub struct SchemaCheck {
+    pub parses: bool,
+    pub violations: Vec<SchemaViolation>,
+}
+
+impl SchemaCheck {
+    pub fn valid(&self) -> bool {
+        self.parses && self.violations.is_empty()
+    }
+}
+
+impl ResponseSchema {
+    pub fn compile(schema: &serde_json::Value) -> Result<Self> {
+        let compiled = JSONSchema::compile(schema)
+            .map_err(|e| EvalError::ConfigError(format!("invalid response schema: {}", e)))?;
+        Ok(Self { compiled })
+    }
+    
+    /// Parse the output as JSON and validate it. A parse failure is reported as a
+    /// single violation at the root carrying the parser's message.
+    pub fn check(&self, output: &str) -> SchemaCheck {
+        let value = match parse_json_output(output) {
+            Ok(value) => value,
+            Err(e) => {
+                return SchemaCheck {
+                    parses: false,
+                    violations: vec![SchemaViolation {
+                        path: "/".to_string(),
+                        message: format!("not valid JSON: {}", e),
+                    }],
+                };
+            }
+        };
+        
+        let violations = match self.compiled.validate(&value) {
+            Ok(()) => Vec::new(),
+            Err(errors) => errors
+                .map(|error| {
+                    let path = error.instance_path.to_string();
+                    SchemaViolation {
+                        path: if path.is_empty() { "/".to_string() } else { path },
+                        message: error.to_string(),
+                    }
+                })
+                .collect(),
+        };
+        
+        SchemaCheck { parses: true, violations }
+    }
+}
+
+/// Parse model output as JSON. Models without a JSON mode often wrap the object in a
+/// markdown code fence, so a single surrounding fence is stripped before giving up.
+pub fn parse_json_output(output: &str) -> std::result::Result<serde_json::Value, serde_json::Error> {
+    let trimmed = output.trim();
+    let error = match serde_json::from_str(trimmed) {
+        Ok(value) => return Ok(value),
+        Err(e) => e,
+    };
+    
+    let fenced = trimmed.strip_prefix("```")
+        .and_then(|rest| rest.strip_suffix("```"))
+        .and_then(|inner| inner.split_once('\n'))
+        .map(|(_language, body)| body);
+    
+    match fenced {
+        Some(body) => serde_json::from_str(body.trim()),
+        None => Err(error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    
+    fn person_schema() -> ResponseSchema {
+        ResponseSchema::compile(&json!({
+            "type": "object",
+            "properties": {
+                "name": {"type": "string"},
+                "age": {"type": "integer", "minimum": 0},
+                "tags": {"type": "array", "items": {"type": "string"}}
+            },
+            "required": ["name", "age"]
+        })).unwrap()
+    }
+    
+    #[test]
+    fn conforming_output_is_valid() {
+        let check = person_schema().check(r#"{"name": "Ada", "age": 36, "tags": ["math"]}"#);
+        assert!(check.parses);
+        assert!(check.violations.is_empty());
+        assert!(check.valid());
+    }
+    
+    #[test]
+    fn fenced_output_is_unwrapped() {
+        let check = person_schema().check("```json\n{\"name\": \"Ada\", \"age\": 36}\n```");
+        assert!(check.valid());
+    }
+    
+    #[test]
+    fn invalid_json_is_a_single_root_violation() {
+        let check = person_schema().check("Sure! Here is the person: Ada, 36");
+        assert!(!check.parses);
+        assert!(!check.valid());
+        assert_eq!(check.violations.len(), 1);
+        assert_eq!(check.violations[0].path, "/");
+        assert!(check.violations[0].message.starts_with("not valid JSON"));
+    }
+    
+    #[test]
+    fn violations_carry_instance_paths() {
+        let check = person_schema().check(r#"{"name": "Ada", "age": -1, "tags": ["math", 7]}"#);
+        assert!(check.parses);
+        assert!(!check.valid());
+        
+        let mut paths: Vec<&str> = check.violations.iter().map(|v| v.path.as_str()).collect();
+        paths.sort();
+        assert_eq!(paths, ["/age", "/tags/1"]);
+    }
+    
+    #[test]
+    fn missing_required_fields_are_reported_at_the_root() {
+        let check = person_schema().check(r#"{"name": "Ada"}"#);
+        assert_eq!(check.violations.len(), 1);
+        assert_eq!(check.violations[0].path, "/");
+        assert!(check.violations[0].message.contains("age"));
+    }
+    
+    #[test]
+    fn wrong_root_type_is_a_violation_not_a_parse_failure() {
+        let check = person_schema().check("[1, 2, 3]");
+        assert!(check.parses);
+        assert!(!check.valid());
+    }
+    
+    #[test]
+    fn invalid_schema_is_a_config_error() {
+        let error = ResponseSchema::compile(&json!({"type": "not-a-type"})).err().unwrap();
+        assert!(matches!(error, EvalError::ConfigError(_)));
+    }
+    
+    #[test]
+    fn parse_json_output_only_strips_a_surrounding_fence() {
+        assert_eq!(parse_json_output("  {\"a\": 1}  ").unwrap(), json!({"a": 1}));
+        assert_eq!(parse_json_output("```\n[1]\n```").unwrap(), json!([1]));
+        assert!(parse_json_output("Result:\n```json\n{\"a\": 1}\n```").is_err());
+        assert!(parse_json_output("```{\"a\": 1}```").is_err());
+    }
+}
\ No newline at end of file
@@ -64,11 +64,44 @@ async fn setup_batch_settings() -> Result<BatchSettings> {
         .default("120".to_string())
         .interact_text()?;
     
+    let streaming = Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt("Stream responses to measure time-to-first-token?")
+        .default(false)
+        .interact()?;
+    
+    let repetitions: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("Samples per prompt and model (more than 1 measures variance)")
+        .default("1".to_string())
+        .validate_with(|input: &String| -> Result<(), &str> {
+            match input.parse::<u32>() {
+                Ok(n) if n > 0 => Ok(()),
+                _ => Err("Must be a number of at least 1"),
+            }
+        })
+        .interact_text()?;
+    
+    let max_cost_usd: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("Cost budget in USD (leave empty for none)")
+        .allow_empty(true)
+        .validate_with(|input: &String| -> Result<(), &str> {
+            match input.trim() {
+                "" => Ok(()),
+                value => match value.parse::<f64>() {
+                    Ok(n) if n > 0.0 => Ok(()),
+                    _ => Err("Must be a positive amount"),
+                },
+            }
+        })
+        .interact_text()?;
+    
     Ok(BatchSettings {
         parallel_execution,
         max_concurrent: max_concurrent.parse().unwrap_or(5),
         retry_attempts: retry_attempts.parse().unwrap_or(3),
         timeout_seconds: timeout_seconds.parse().unwrap_or(120),
+        streaming,
+        repetitions: repetitions.parse().unwrap_or(1),
+        max_cost_usd: max_cost_usd.trim().parse().ok(),
     })
 }
 
@@ -79,6 +112,7 @@ async fn setup_non_interactive(args: SetupArgs) -> Result<()> {
         providers: Vec::new(),
         analysis_options: AnalysisOptions::default(),
         batch_settings: BatchSettings::default(),
+        credential_profile: None,
     };
     
     let evaluation = Evaluation::new(args.name.clone(), config);
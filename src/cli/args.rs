@@ -52,10 +52,18 @@ pub enum Commands {
     #[command(
         about = "Show evaluation results",
         long_about = "Show evaluation results with optional web interface or export options.",
-        after_help = "EXAMPLES:\n    evaleds show model-comparison          # Show results summary\n    evaleds show model-comparison --web    # Launch web interface\n    evaleds show model-comparison --export markdown -o report.md"
+        after_help = "EXAMPLES:\n    evaleds show model-comparison          # Show results summary\n    evaleds show model-comparison --web    # Launch web interface\n    evaleds show model-comparison --run 3  # Show an earlier run\n    evaleds show model-comparison --export markdown -o report.md"
     )]
     Show(ShowArgs),
     
+    /// List the runs of an evaluation
+    #[command(
+        about = "List the runs of an evaluation",
+        long_about = "List every stored run of an evaluation with its status, results summary and the environment it ran in. Each 'run' is kept alongside earlier ones; pass a run number to 'show --run' to view it.",
+        after_help = "EXAMPLES:\n    evaleds runs model-comparison\n    evaleds show model-comparison --run 2"
+    )]
+    Runs(RunsArgs),
+    
     /// List evaluations
     #[command(
         about = "List evaluations",
@@ -87,6 +95,22 @@ pub enum Commands {
         after_help = "EXAMPLES:\n    evaleds copy model-comparison extended-comparison\n    evaleds copy baseline experiment-v2"
     )]
     Copy(CopyArgs),
+    
+    /// Inspect, test and enable providers
+    #[command(
+        about = "Inspect, test and enable providers",
+        long_about = "Inspect configured providers: which ones are available and why others were skipped, whether their credentials work, and which models they offer. Model listings are merged from config and the provider's model-listing endpoint, and cached in a local catalog.",
+        after_help = "EXAMPLES:\n    evaleds providers list\n    evaleds providers test anthropic\n    evaleds providers test openai --model gpt-4o-mini\n    evaleds providers enable local\n    evaleds providers models vllm --refresh"
+    )]
+    Providers(ProvidersArgs),
+    
+    /// Manage API key profiles
+    #[command(
+        about = "Manage API key profiles",
+        long_about = "Manage named API key profiles stored in credentials.toml next to the user config. Select a profile with credential_profile in config or .evaleds.toml, EVALEDS_PROFILE, or per evaluation with create --profile. A selected profile takes precedence over API key environment variables.",
+        after_help = "EXAMPLES:\n    evaleds credentials add team-prod openai\n    evaleds credentials add personal anthropic --key sk-ant-...\n    evaleds credentials list\n    evaleds credentials remove team-prod openai\n    evaleds credentials remove personal"
+    )]
+    Credentials(CredentialsArgs),
 }
 
 #[derive(Args)]
@@ -113,6 +137,10 @@ pub struct CreateArgs {
     /// Configuration from file
     #[arg(short, long)]
     pub file: Option<String>,
+    
+    /// Credential profile to run with, overriding the one selected in config
+    #[arg(long)]
+    pub profile: Option<String>,
 }
 
 #[derive(Args)]
@@ -120,10 +148,14 @@ pub struct RunArgs {
     /// Name of the evaluation to run
     pub name: String,
     
-    /// Force re-run even if results exist
+    /// Start a new run even if the latest one completed or was interrupted
     #[arg(short, long)]
     pub force: bool,
     
+    /// Continue the latest run, executing only cells without a successful result
+    #[arg(long, conflicts_with = "force")]
+    pub resume: bool,
+    
     /// Run in background
     #[arg(long)]
     pub background: bool,
@@ -135,6 +167,30 @@ pub struct RunArgs {
     /// Dry run - show what would be executed
     #[arg(long)]
     pub dry_run: bool,
+    
+    /// Record all provider HTTP traffic into cassette files in this directory
+    #[arg(long, value_name = "DIR", conflicts_with = "replay")]
+    pub record: Option<String>,
+    
+    /// Serve provider responses from cassettes in this directory (no network access)
+    #[arg(long, value_name = "DIR")]
+    pub replay: Option<String>,
+    
+    /// Call every provider without the response cache
+    #[arg(long, conflicts_with = "refresh_cache")]
+    pub no_cache: bool,
+    
+    /// Call every provider and replace the cached responses
+    #[arg(long)]
+    pub refresh_cache: bool,
+    
+    /// Cost budget in USD, overriding max_cost_usd from the batch settings
+    #[arg(long, value_name = "USD")]
+    pub budget: Option<f64>,
+    
+    /// Start even when the estimated cost exceeds the budget
+    #[arg(short, long)]
+    pub yes: bool,
 }
 
 #[derive(Args)]
@@ -161,6 +217,16 @@ pub struct ShowArgs {
     /// Export output file
     #[arg(short, long)]
     pub output: Option<String>,
+    
+    /// Run number to show instead of the latest run
+    #[arg(long, value_name = "N")]
+    pub run: Option<u32>,
+}
+
+#[derive(Args)]
+pub struct RunsArgs {
+    /// Name of the evaluation
+    pub name: String,
 }
 
 #[derive(Args)]
@@ -221,4 +287,98 @@ pub struct CopyArgs {
     
     /// Destination evaluation name
     pub destination: String,
+}
+
+#[derive(Args)]
+pub struct ProvidersArgs {
+    #[command(subcommand)]
+    pub command: ProvidersCommand,
+}
+
+#[derive(Subcommand)]
+pub enum ProvidersCommand {
+    /// Show which providers are available and why others were skipped
+    List,
+    
+    /// Send a small probe request and report latency and auth status
+    Test(ProviderTestArgs),
+    
+    /// List the models a provider offers
+    Models(ProviderModelsArgs),
+    
+    /// Enable a provider in config
+    Enable(ProviderNameArgs),
+    
+    /// Disable a provider in config
+    Disable(ProviderNameArgs),
+}
+
+#[derive(Args)]
+pub struct CredentialsArgs {
+    #[command(subcommand)]
+    pub command: CredentialsCommand,
+}
+
+#[derive(Subcommand)]
+pub enum CredentialsCommand {
+    /// Store a provider's API key in a profile
+    Add(CredentialAddArgs),
+    
+    /// Remove a provider's key, or a whole profile
+    Remove(CredentialRemoveArgs),
+    
+    /// List profiles and the providers they hold keys for
+    List,
+}
+
+#[derive(Args)]
+pub struct CredentialAddArgs {
+    /// Profile name, e.g. team-prod
+    pub profile: String,
+    
+    /// Provider the key is for
+    pub provider: String,
+    
+    /// API key; prompted for without echo when omitted, keeping it out of shell history
+    #[arg(long)]
+    pub key: Option<String>,
+}
+
+#[derive(Args)]
+pub struct CredentialRemoveArgs {
+    /// Profile name
+    pub profile: String,
+    
+    /// Provider whose key to remove; the whole profile when omitted
+    pub provider: Option<String>,
+    
+    /// Skip confirmation when removing a whole profile
+    #[arg(short, long)]
+    pub force: bool,
+}
+
+#[derive(Args)]
+pub struct ProviderTestArgs {
+    /// Provider to test; every available provider when omitted
+    pub provider: Option<String>,
+    
+    /// Model to probe instead of the provider's default model
+    #[arg(short, long)]
+    pub model: Option<String>,
+}
+
+#[derive(Args)]
+pub struct ProviderNameArgs {
+    /// Name of the provider
+    pub provider: String,
+}
+
+#[derive(Args)]
+pub struct ProviderModelsArgs {
+    /// Name of the provider
+    pub provider: String,
+    
+    /// Query the provider's model-listing endpoint even if the cached catalog is fresh
+    #[arg(short, long)]
+    pub refresh: bool,
 }
\ No newline at end of file
@@ -8,6 +8,8 @@ use crate::ui::{interactive_style::*, output_formatting::*};
 // CREATE COMMAND (aligned with PromptEds create)
 pub mod create {
     use super::*;
+    use crate::core::catalog::ModelCatalog;
+    use super::providers::{refresh_catalog, format_catalog_model};
     
     pub async fn execute(args: CreateArgs) -> Result<()> {
         let config = load_config().await?;
@@ -40,6 +42,8 @@ pub mod create {
             evaluation.category = Some(category);
         }
         
+        evaluation.config.credential_profile = args.profile;
+        
         storage.save_evaluation(&evaluation).await?;
         
         display_completion("Created evaluation", &args.name);
@@ -101,6 +105,7 @@ pub mod create {
             providers,
             analysis_options,
             batch_settings: BatchSettings::default(),
+            credential_profile: args.profile.clone(),
         });
         
         evaluation.description = description;
@@ -125,9 +130,58 @@ pub mod create {
         Ok(Vec::new())
     }
     
+    /// Pick providers, then models for each from the model catalog
     async fn setup_providers_interactive() -> Result<Vec<ProviderConfig>> {
-        // Implementation here...
-        Ok(Vec::new())
+        let config = load_config().await?;
+        
+        let mut names: Vec<String> = config.providers.iter()
+            .filter(|(_, info)| info.enabled)
+            .map(|(name, _)| name.clone())
+            .collect();
+        names.sort();
+        
+        if names.is_empty() {
+            display_warning("No providers are enabled");
+            return Ok(Vec::new());
+        }
+        
+        let selected = prompt_multi_select("Providers", &names, None)?;
+        let selected: Vec<&String> = selected.into_iter().map(|index| &names[index]).collect();
+        
+        // Refresh stale listings so newly pulled or deployed models show up
+        let mut catalog = ModelCatalog::load().await?;
+        let stale: Vec<&String> = selected.iter()
+            .filter(|name| catalog.is_stale(name))
+            .cloned()
+            .collect();
+        if !stale.is_empty() {
+            refresh_catalog(&mut catalog, &stale).await?;
+        }
+        
+        let mut providers = Vec::new();
+        for name in selected {
+            let info = &config.providers[name];
+            let models = catalog.models(name, info);
+            if models.is_empty() {
+                display_warning(&format!("No models known for '{}'; add available_models to its config", name));
+                continue;
+            }
+            
+            let labels: Vec<String> = models.iter().map(format_catalog_model).collect();
+            let defaults: Vec<bool> = models.iter().map(|m| m.is_default).collect();
+            let chosen = prompt_multi_select(&format!("Models for {}", name), &labels, Some(&defaults))?;
+            if chosen.is_empty() {
+                continue;
+            }
+            
+            providers.push(ProviderConfig {
+                name: name.clone(),
+                models: chosen.into_iter().map(|index| models[index].name.clone()).collect(),
+                settings: ModelSettings::default(),
+            });
+        }
+        
+        Ok(providers)
     }
     
     async fn setup_analysis_interactive() -> Result<AnalysisOptions> {
@@ -215,9 +269,18 @@ pub mod show {
     
     pub async fn execute(args: ShowArgs) -> Result<()> {
         let storage = Storage::new().await?;
-        let evaluation = storage.load_evaluation(&args.name).await?
+        let mut evaluation = storage.load_evaluation(&args.name).await?
             .ok_or_else(|| EvalError::NotFound(args.name.clone()))?;
         
+        // Everything below shows the selected run as if it were the evaluation's only one
+        if let Some(number) = args.run {
+            let run = storage.load_run(&evaluation.id, Some(number)).await?
+                .ok_or_else(|| EvalError::ConfigError(format!(
+                    "evaluation '{}' has no run #{}; see 'evaleds runs {}'", args.name, number, args.name
+                )))?;
+            evaluation = evaluation.at_run(&run);
+        }
+        
         let use_color = should_use_colors(false); // Would get from global args
         
         if args.raw {
@@ -234,8 +297,9 @@ pub mod show {
         }
         
         if args.web {
-            // Launch web interface
-            return launch_web_interface(&evaluation).await;
+            // Launch web interface, with every run available in its run picker
+            let runs = storage.list_runs(&evaluation.id).await?;
+            return launch_web_interface(&evaluation, runs, args.run).await;
         }
         
         if let Some(export_format) = args.export {
@@ -250,21 +314,25 @@ pub mod show {
         Ok(())
     }
     
-    async fn launch_web_interface(evaluation: &Evaluation) -> Result<()> {
+    async fn launch_web_interface(evaluation: &Evaluation, runs: Vec<EvaluationRun>, run: Option<u32>) -> Result<()> {
         display_info("Launching web interface...");
         
-        // Implementation would start web server here
-        // let server = WebServer::new(evaluation, 0).await?;
-        // let url = server.start().await?;
-        
-        let url = "http://localhost:8080"; // Placeholder
-        println!("🌐 Web interface available at: {}", format_metric(url, true));
+        let server = crate::web::server::WebServer::new(evaluation, runs, 0).await?;
+        let mut url = server.start().await?;
+        if let Some(number) = run {
+            url = format!("{}/?run={}", url, number);
+        }
+        println!("🌐 Web interface available at: {}", format_metric(&url, true));
         
         // Open browser if available
-        if let Err(_) = webbrowser::open(url) {
+        if webbrowser::open(&url).is_err() {
             display_info("Open the URL above in your browser to view results");
         }
         
+        // The server runs in the background until the command is stopped
+        display_info("Press Ctrl-C to stop the web interface");
+        tokio::signal::ctrl_c().await?;
+        
         Ok(())
     }
     
@@ -298,6 +366,29 @@ pub mod show {
     }
 }
 
+// RUNS COMMAND
+pub mod runs {
+    use super::*;
+    
+    pub async fn execute(args: RunsArgs) -> Result<()> {
+        let storage = Storage::new().await?;
+        let evaluation = storage.load_evaluation(&args.name).await?
+            .ok_or_else(|| EvalError::NotFound(args.name.clone()))?;
+        
+        let runs = storage.list_runs(&evaluation.id).await?;
+        if runs.is_empty() {
+            println!("Evaluation '{}' has not been run yet.", args.name);
+            display_help_suggestion("run", true);
+            return Ok(());
+        }
+        
+        let use_color = should_use_colors(false); // Would get from global args
+        display_run_list(&runs, use_color)?;
+        
+        Ok(())
+    }
+}
+
 // LIST COMMAND (aligned with PromptEds list)
 pub mod list {
     use super::*;
@@ -447,6 +538,19 @@ pub mod edit {
             evaluation.tags = new_tags_str.split(',').map(|s| s.trim().to_string()).collect();
         }
         
+        // Edit credential profile ("-" clears it)
+        let new_profile = prompt_text(
+            "Credential profile (- for none)",
+            evaluation.config.credential_profile.as_deref(),
+            false
+        )?;
+        
+        match new_profile.trim() {
+            "" => {}
+            "-" => evaluation.config.credential_profile = None,
+            profile => evaluation.config.credential_profile = Some(profile.to_string()),
+        }
+        
         // Save changes
         storage.update_evaluation(&evaluation).await?;
         
@@ -486,6 +590,283 @@ pub mod copy {
         display_success(&format!("Copied '{}' to '{}'", args.source, args.destination));
         display_next_steps(&args.destination);
         
+        Ok(())
+    }
+}
+
+// PROVIDERS COMMAND
+pub mod providers {
+    use super::*;
+    use crate::core::catalog::{ModelCatalog, CatalogModel};
+    use crate::core::providers::{ProviderManager, BUILTIN_PROVIDERS};
+    
+    pub async fn execute(args: ProvidersArgs) -> Result<()> {
+        match args.command {
+            ProvidersCommand::List => execute_list().await,
+            ProvidersCommand::Test(args) => execute_test(args).await,
+            ProvidersCommand::Models(args) => execute_models(args).await,
+            ProvidersCommand::Enable(args) => execute_enable(args).await,
+            ProvidersCommand::Disable(args) => execute_disable(args).await,
+        }
+    }
+    
+    /// Configured providers plus the built-in ones, sorted by name
+    fn known_providers(config: &EvalEdsConfig) -> Vec<String> {
+        let mut names: Vec<String> = config.providers.keys().cloned()
+            .chain(BUILTIN_PROVIDERS.iter().map(|name| name.to_string()))
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+    
+    async fn execute_list() -> Result<()> {
+        let config = load_config().await?;
+        let manager = ProviderManager::new().await?;
+        let use_color = should_use_colors(false); // Would get from global args
+        let available = manager.get_available_providers();
+        
+        if let Some(profile) = manager.credential_profile() {
+            println!("Credential profile: {}\n", profile);
+        }
+        
+        println!("Providers:");
+        for name in known_providers(&config) {
+            let kind = config.providers.get(&name)
+                .map(|info| format!("{:?}", info.kind).to_lowercase())
+                .unwrap_or_else(|| "builtin".to_string());
+            
+            let status = if available.contains(&name) {
+                let models = config.providers.get(&name).map_or(0, |info| info.available_models.len());
+                format!("ready ({} configured models)", models)
+            } else {
+                format!("skipped: {}", manager.skip_reason(&name).unwrap_or("not configured"))
+            };
+            
+            println!("  {:<16} {:<18} {}", format_evaluation_name(&name, use_color), kind, status);
+        }
+        
+        Ok(())
+    }
+    
+    async fn execute_test(args: ProviderTestArgs) -> Result<()> {
+        let config = load_config().await?;
+        let manager = ProviderManager::new().await?;
+        
+        let providers = match &args.provider {
+            Some(provider) => vec![provider.clone()],
+            None => {
+                let mut available = manager.get_available_providers();
+                available.sort();
+                available
+            }
+        };
+        
+        if providers.is_empty() {
+            println!("No providers available to test. Run 'evaleds providers list' to see why.");
+            return Ok(());
+        }
+        
+        let mut failures = 0;
+        for provider in &providers {
+            let model = args.model.clone()
+                .or_else(|| config.providers.get(provider).map(|info| info.default_model.clone()))
+                .unwrap_or_default();
+            if model.is_empty() {
+                println!("  {:<16} {:<12} no model to probe; pass --model", provider, "skipped");
+                failures += 1;
+                continue;
+            }
+            
+            match manager.probe(provider, &model).await {
+                Ok(latency) => {
+                    println!("  {:<16} {:<12} {} in {}ms", provider, "ok", model, latency.as_millis());
+                }
+                Err(EvalError::ApiError(e)) if e.kind == ApiErrorKind::Authentication => {
+                    println!("  {:<16} {:<12} {}", provider, "auth failed", e.message);
+                    failures += 1;
+                }
+                Err(e) => {
+                    println!("  {:<16} {:<12} {}", provider, "failed", e);
+                    failures += 1;
+                }
+            }
+        }
+        
+        if failures > 0 {
+            return Err(EvalError::ProviderError(format!("{} of {} providers failed the probe", failures, providers.len())));
+        }
+        
+        Ok(())
+    }
+    
+    async fn execute_enable(args: ProviderNameArgs) -> Result<()> {
+        ensure_configured(&args.provider).await?;
+        crate::utils::config::enable_provider(&args.provider).await?;
+        display_completion("Enabled provider", &args.provider);
+        Ok(())
+    }
+    
+    async fn execute_disable(args: ProviderNameArgs) -> Result<()> {
+        ensure_configured(&args.provider).await?;
+        crate::utils::config::disable_provider(&args.provider).await?;
+        display_completion("Disabled provider", &args.provider);
+        Ok(())
+    }
+    
+    /// `enable_provider` reports a missing provider as NotFound, which reads as a missing evaluation
+    async fn ensure_configured(provider: &str) -> Result<()> {
+        let config = load_config().await?;
+        if !config.providers.contains_key(provider) {
+            return Err(EvalError::ConfigError(format!("provider '{}' is not configured", provider)));
+        }
+        Ok(())
+    }
+    
+    async fn execute_models(args: ProviderModelsArgs) -> Result<()> {
+        let config = load_config().await?;
+        let info = config.providers.get(&args.provider)
+            .ok_or_else(|| EvalError::ConfigError(format!("provider '{}' is not configured", args.provider)))?;
+        
+        let mut catalog = ModelCatalog::load().await?;
+        if args.refresh || catalog.is_stale(&args.provider) {
+            refresh_catalog(&mut catalog, &[&args.provider]).await?;
+        }
+        
+        let use_color = should_use_colors(false); // Would get from global args
+        let models = catalog.models(&args.provider, info);
+        
+        if models.is_empty() {
+            println!("No models known for '{}'.", args.provider);
+            return Ok(());
+        }
+        
+        println!("Models for {}:", format_evaluation_name(&args.provider, use_color));
+        for model in &models {
+            println!("  {}", format_catalog_model(model));
+        }
+        
+        if let Some(entry) = catalog.entry(&args.provider) {
+            println!("\nCatalog refreshed {}", format_timestamp(&entry.refreshed_at, use_color));
+        }
+        
+        Ok(())
+    }
+    
+    /// Refresh catalog entries and save. Unreachable providers or missing API keys are
+    /// reported as warnings; the configured models are still offered.
+    pub async fn refresh_catalog(catalog: &mut ModelCatalog, providers: &[&String]) -> Result<()> {
+        let manager = match ProviderManager::new().await {
+            Ok(manager) => manager,
+            Err(e) => {
+                display_warning(&format!("Could not refresh model catalog: {}", e));
+                return Ok(());
+            }
+        };
+        
+        for provider in providers {
+            if let Err(e) = catalog.refresh(&manager, provider).await {
+                display_warning(&format!("Could not list models for '{}': {}", provider, e));
+            }
+        }
+        
+        catalog.save().await
+    }
+    
+    pub fn format_catalog_model(model: &CatalogModel) -> String {
+        let mut sources = Vec::new();
+        if model.configured {
+            sources.push("configured");
+        }
+        if model.discovered {
+            sources.push("discovered");
+        }
+        
+        let default_marker = if model.is_default { " (default)" } else { "" };
+        format!("{}{} [{}]", model.name, default_marker, sources.join(", "))
+    }
+}
+
+// CREDENTIALS COMMAND
+pub mod credentials {
+    use super::*;
+    use crate::utils::credentials::{CredentialStore, mask_key};
+    
+    pub async fn execute(args: CredentialsArgs) -> Result<()> {
+        match args.command {
+            CredentialsCommand::Add(args) => execute_add(args).await,
+            CredentialsCommand::Remove(args) => execute_remove(args).await,
+            CredentialsCommand::List => execute_list().await,
+        }
+    }
+    
+    async fn execute_add(args: CredentialAddArgs) -> Result<()> {
+        let key = match args.key {
+            Some(key) => key,
+            None => prompt_password(&format!("{} API key for profile '{}'", args.provider, args.profile))?,
+        };
+        let key = key.trim().to_string();
+        if key.is_empty() {
+            return Err(EvalError::ConfigError("API key is empty".to_string()));
+        }
+        
+        let mut store = CredentialStore::load().await?;
+        store.set_key(&args.profile, &args.provider, key);
+        store.save().await?;
+        
+        display_success(&format!("Stored {} key in profile '{}'", args.provider, args.profile));
+        Ok(())
+    }
+    
+    async fn execute_remove(args: CredentialRemoveArgs) -> Result<()> {
+        let mut store = CredentialStore::load().await?;
+        
+        let removed = match &args.provider {
+            Some(provider) => store.remove_key(&args.profile, provider),
+            None => {
+                if !args.force && !confirm_destructive_action("delete credential profile", &args.profile)? {
+                    display_info("Removal cancelled");
+                    return Ok(());
+                }
+                store.remove_profile(&args.profile)
+            }
+        };
+        
+        if !removed {
+            return Err(EvalError::ConfigError(match &args.provider {
+                Some(provider) => format!("credential profile '{}' has no {} key", args.profile, provider),
+                None => format!("credential profile '{}' not found", args.profile),
+            }));
+        }
+        
+        store.save().await?;
+        
+        match &args.provider {
+            Some(provider) => display_success(&format!("Removed {} key from profile '{}'", provider, args.profile)),
+            None => display_success(&format!("Removed credential profile '{}'", args.profile)),
+        }
+        Ok(())
+    }
+    
+    async fn execute_list() -> Result<()> {
+        let store = CredentialStore::load().await?;
+        let config = load_config().await?;
+        let use_color = should_use_colors(false); // Would get from global args
+        
+        if store.profiles.is_empty() {
+            println!("No credential profiles. Add one with 'evaleds credentials add <profile> <provider>'.");
+            return Ok(());
+        }
+        
+        for (profile, keys) in &store.profiles {
+            let selected = if config.credential_profile.as_deref() == Some(profile.as_str()) { " (selected)" } else { "" };
+            println!("{}{}", format_evaluation_name(profile, use_color), selected);
+            for (provider, key) in keys {
+                println!("  {:<16} {}", provider, mask_key(key));
+            }
+        }
+        
+        println!("\nStored in {}", CredentialStore::path()?.display());
         Ok(())
     }
 }
\ No newline at end of file
@@ -1,7 +1,9 @@
 // Extended evaluation types to support PromptEds alignment
+use crate::utils::error::{Result, EvalError};
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
-use std::collections::HashMap;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use std::collections::{BTreeMap, HashMap};
 
 // Extended Evaluation with PromptEds-style metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +23,39 @@ pub struct Evaluation {
     pub author: Option<String>,               // Creator metadata
 }
 
+// One run of an evaluation. Every run is kept, so the same evaluation can be compared
+// week over week as providers update their models.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvaluationRun {
+    pub id: String,
+    pub evaluation_id: String,
+    pub number: u32,                          // 1-based, in the order runs were started
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub status: EvaluationStatus,
+    pub config: EvaluationConfig,             // Snapshot taken when the run started
+    #[serde(default)]
+    pub environment: RunEnvironment,
+    pub results: Option<EvaluationResults>,
+}
+
+// Where and how a run was executed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RunEnvironment {
+    #[serde(default)]
+    pub evaleds_version: String,
+    #[serde(default)]
+    pub os: String,
+    #[serde(default)]
+    pub arch: String,
+    #[serde(default)]
+    pub credential_profile: Option<String>,
+    #[serde(default)]
+    pub cassette: Option<String>,             // Directory the run recorded to or replayed from
+    #[serde(default)]
+    pub response_cache: bool,                 // Results could be served from the local response cache
+}
+
 // Extended EvaluationSummary for list commands
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EvaluationSummary {
@@ -40,6 +75,591 @@ pub struct EvaluationSummary {
     pub author: Option<String>,
 }
 
+// Extended EvaluationConfig with a credential profile
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EvaluationConfig {
+    pub prompts: Vec<PromptConfig>,
+    pub providers: Vec<ProviderConfig>,
+    pub analysis_options: AnalysisOptions,
+    pub batch_settings: BatchSettings,
+    #[serde(default)]
+    pub credential_profile: Option<String>,   // Overrides the profile selected in config
+}
+
+// Extended PromptConfig with multi-turn chat support
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptConfig {
+    pub source: PromptSource,
+    pub variables: HashMap<String, String>,
+    pub variations: Vec<Variation>,
+    #[serde(default)]
+    pub messages: Vec<ChatMessage>,           // System prompt and prior turns, sent before the rendered prompt
+    #[serde(default)]
+    pub tools: Vec<ToolDefinition>,           // Tools offered to the model
+    #[serde(default)]
+    pub expected_tool_calls: Vec<ExpectedToolCall>,  // Calls the model should make, for scoring
+    #[serde(default)]
+    pub response_schema: Option<serde_json::Value>,  // JSON Schema the output must conform to
+    #[serde(default)]
+    pub images: Vec<ImageAttachment>,         // Sent with the rendered prompt
+    #[serde(default)]
+    pub repetitions: Option<u32>,             // Overrides BatchSettings.repetitions for this prompt
+}
+
+// Image attached to a prompt, referenced by local file path
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImageAttachment {
+    pub path: String,
+    #[serde(default)]
+    pub media_type: Option<String>,           // Guessed from the file extension when unset
+}
+
+impl ImageAttachment {
+    /// Read and base64-encode the file, failing on anything that isn't an image
+    pub async fn load(&self) -> Result<ImageData> {
+        let media_type = match &self.media_type {
+            Some(media_type) => media_type.clone(),
+            None => mime_guess::from_path(&self.path)
+                .first()
+                .map(|mime| mime.essence_str().to_string())
+                .unwrap_or_default(),
+        };
+        if !media_type.starts_with("image/") {
+            return Err(EvalError::ConfigError(format!(
+                "cannot tell the image type of '{}'; set media_type on the attachment", self.path
+            )));
+        }
+        
+        let bytes = tokio::fs::read(&self.path).await
+            .map_err(|e| EvalError::ConfigError(format!("cannot read image '{}': {}", self.path, e)))?;
+        
+        Ok(ImageData {
+            path: self.path.clone(),
+            media_type,
+            data: BASE64.encode(bytes),
+        })
+    }
+}
+
+/// Load a prompt's attachments in order
+pub async fn load_images(attachments: &[ImageAttachment]) -> Result<Vec<ImageData>> {
+    let mut images = Vec::with_capacity(attachments.len());
+    for attachment in attachments {
+        images.push(attachment.load().await?);
+    }
+    Ok(images)
+}
+
+// Tool the model may call, described with a JSON Schema for its arguments
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default = "empty_object_schema")]
+    pub parameters: serde_json::Value,
+}
+
+fn empty_object_schema() -> serde_json::Value {
+    serde_json::json!({"type": "object", "properties": {}})
+}
+
+// Tool call returned by a model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolCall {
+    #[serde(default)]
+    pub id: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub arguments: serde_json::Value,         // Parsed JSON; left as a string if the model sent invalid JSON
+}
+
+// A call the model is expected to make
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExpectedToolCall {
+    pub name: String,
+    #[serde(default)]
+    pub arguments: Option<serde_json::Value>, // Values to check; unset only checks the tool name
+    #[serde(default)]
+    pub exact_arguments: bool,                // Fail on extra arguments instead of checking only the listed ones
+}
+
+// Single chat message in a multi-turn prompt
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub role: MessageRole,
+    pub content: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub images: Vec<ImageData>,               // Loaded attachments; only set on the rendered prompt
+}
+
+// Image loaded from an attachment, ready to send
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImageData {
+    pub path: String,
+    pub media_type: String,
+    pub data: String,                         // Base64-encoded file contents
+}
+
+impl ImageData {
+    pub fn data_url(&self) -> String {
+        format!("data:{};base64,{}", self.media_type, self.data)
+    }
+    
+    pub fn attachment(&self) -> ImageAttachment {
+        ImageAttachment {
+            path: self.path.clone(),
+            media_type: Some(self.media_type.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageRole {
+    System,
+    User,
+    Assistant,
+}
+
+impl MessageRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MessageRole::System => "system",
+            MessageRole::User => "user",
+            MessageRole::Assistant => "assistant",
+        }
+    }
+}
+
+impl ChatMessage {
+    pub fn new(role: MessageRole, content: impl Into<String>) -> Self {
+        Self { role, content: content.into(), images: Vec::new() }
+    }
+    
+    pub fn with_images(mut self, images: Vec<ImageData>) -> Self {
+        self.images = images;
+        self
+    }
+    
+    pub fn system(content: impl Into<String>) -> Self {
+        Self::new(MessageRole::System, content)
+    }
+    
+    pub fn user(content: impl Into<String>) -> Self {
+        Self::new(MessageRole::User, content)
+    }
+    
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self::new(MessageRole::Assistant, content)
+    }
+}
+
+/// Render a message list as plain text for storage and reports.
+/// A lone user message is stored as-is so single-turn results look unchanged.
+/// Images are listed by path after the message text.
+pub fn render_transcript(messages: &[ChatMessage]) -> String {
+    if let [message] = messages {
+        if message.role == MessageRole::User && message.images.is_empty() {
+            return message.content.clone();
+        }
+    }
+    
+    messages.iter()
+        .map(|m| {
+            let mut rendered = format!("[{}]\n{}", m.role.as_str(), m.content);
+            for image in &m.images {
+                rendered.push_str(&format!("\n[image: {}]", image.path));
+            }
+            rendered
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// SHA-256 of the rendered transcript and any image contents, hex encoded. Stable across runs.
+pub fn prompt_hash(messages: &[ChatMessage]) -> String {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    hasher.update(render_transcript(messages).as_bytes());
+    for image in messages.iter().flat_map(|m| &m.images) {
+        hasher.update(image.data.as_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Identifies a rendered prompt independent of the model: the same in every run
+pub fn prompt_id(messages: &[ChatMessage]) -> String {
+    prompt_hash(messages)[..16].to_string()
+}
+
+/// Identifies a prompt x model cell: the same for every repetition and every run. A variation
+/// is keyed by its name, so reordering or inserting variations keeps results joined to their cell.
+pub fn cell_id(provider: &str, model: &str, variation: Option<&str>, messages: &[ChatMessage]) -> String {
+    use sha2::{Digest, Sha256};
+    let key = match variation {
+        Some(variation) => format!("{}/{}/{}/{}", provider, model, variation, prompt_hash(messages)),
+        None => format!("{}/{}/{}", provider, model, prompt_hash(messages)),
+    };
+    format!("{:x}", Sha256::digest(key.as_bytes()))[..16].to_string()
+}
+
+// Extended ExecutionResult with tool calls
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionResult {
+    pub id: String,
+    pub prompt_id: String,
+    pub provider: String,
+    pub model: String,
+    pub input: String,
+    pub output: String,
+    pub metadata: ExecutionMetadata,
+    pub status: ExecutionStatus,
+    #[serde(default)]
+    pub tool_calls: Vec<ToolCall>,            // Tool calls returned by the model, in order
+    #[serde(default)]
+    pub expected_tool_calls: Vec<ExpectedToolCall>,  // Copied from the prompt config for scoring
+    #[serde(default)]
+    pub response_schema: Option<serde_json::Value>,  // Copied from the prompt config for conformance checks
+    #[serde(default)]
+    pub images: Vec<ImageAttachment>,         // Images sent with the prompt, without their data
+    #[serde(default)]
+    pub cell_id: String,                      // Shared by all repetitions of the same prompt on the same model
+    #[serde(default)]
+    pub repetition: u32,                      // 0-based sample index within the cell
+    #[serde(default)]
+    pub resolved_prompt: Option<ResolvedPrompt>,  // Which prompt and variation produced the result
+}
+
+// A prompt config rendered for one of its variations
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResolvedPrompt {
+    pub prompt_index: u32,                    // 0-based position in EvaluationConfig.prompts
+    #[serde(default)]
+    pub variation: Option<u32>,               // 0-based position in PromptConfig.variations, for ordering; None for the base variables
+    #[serde(default)]
+    pub variation_name: Option<String>,       // Identifies the variation across runs, wherever it sits in the list
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,  // Values the prompt was rendered with
+    pub content_hash: String,                 // prompt_hash of the rendered messages
+}
+
+impl ResolvedPrompt {
+    /// Numbered from 1 for display, e.g. "prompt 2, variation formal". Results stored
+    /// before variation names were recorded fall back to the variation's number.
+    pub fn label(&self) -> String {
+        match (&self.variation_name, self.variation) {
+            (Some(name), _) => format!("prompt {}, variation {}", self.prompt_index + 1, name),
+            (None, Some(variation)) => format!("prompt {}, variation {}", self.prompt_index + 1, variation + 1),
+            (None, None) => format!("prompt {}", self.prompt_index + 1),
+        }
+    }
+}
+
+// Extended AnalysisResults with tool-call scoring and schema conformance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisResults {
+    pub response_metrics: Vec<ResponseMetrics>,
+    pub similarity_matrix: Vec<SimilarityScore>,
+    pub content_analysis: Vec<ContentAnalysis>,
+    pub quality_indicators: Vec<QualityScore>,
+    pub cost_breakdown: CostBreakdown,
+    pub performance_stats: PerformanceStats,
+    #[serde(default)]
+    pub tool_call_scores: Vec<ToolCallScore>, // Only for prompts with expected tool calls
+    #[serde(default)]
+    pub schema_conformance: Vec<SchemaConformance>,  // One per successful execution of a prompt with a response schema
+    #[serde(default)]
+    pub schema_conformance_by_model: Vec<SchemaConformanceRate>,
+    #[serde(default)]
+    pub cell_variance: Vec<CellVariance>,     // Only for cells sampled more than once
+}
+
+// Per-model tool-calling accuracy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolCallScore {
+    pub provider: String,
+    pub model: String,
+    pub executions: u32,
+    pub expected_calls: u32,
+    pub tool_selection_accuracy: f32,         // Share of expected calls made with the right tool
+    pub argument_accuracy: f32,               // Share of checked arguments that matched, over correctly selected calls
+    pub exact_match_rate: f32,                // Share of executions with every call right and no extra calls
+    pub unexpected_calls: u32,                // Calls beyond the expected ones
+}
+
+// Spread of one metric across a cell's repeated samples
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricSpread {
+    pub mean: f64,
+    pub std_dev: f64,                         // Sample standard deviation
+    pub min: f64,
+    pub max: f64,
+}
+
+// Variation across the repeated samples of one prompt x model cell
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CellVariance {
+    pub cell_id: String,
+    #[serde(default)]
+    pub resolved_prompt: Option<ResolvedPrompt>,
+    pub provider: String,
+    pub model: String,
+    pub samples: u32,
+    pub successful_samples: u32,
+    pub latency_ms: Option<MetricSpread>,
+    pub cost_usd: Option<MetricSpread>,
+    pub quality: Option<MetricSpread>,        // Overall quality score
+    pub self_agreement: Option<f32>,          // Mean pairwise similarity of the outputs, 0-1
+}
+
+// Whether one execution's output parsed as JSON and matched the prompt's response schema
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaConformance {
+    pub execution_id: String,
+    pub provider: String,
+    pub model: String,
+    pub parses: bool,
+    pub valid: bool,
+    pub violations: Vec<SchemaViolation>,     // Empty when valid
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaViolation {
+    pub path: String,                         // JSON Pointer into the output, "/" for the root
+    pub message: String,
+}
+
+// Per-model schema conformance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaConformanceRate {
+    pub provider: String,
+    pub model: String,
+    pub executions: u32,
+    pub parse_rate: f32,                      // Percentage of outputs that parsed as JSON
+    pub valid_rate: f32,                      // Percentage of outputs that validated against the schema
+    pub failed_paths: Vec<String>,            // Paths that failed validation, most frequent first
+}
+
+// Extended ExecutionMetadata with streaming latency metrics and cache hits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionMetadata {
+    pub response_time_ms: u64,
+    pub token_count_input: u32,
+    pub token_count_output: u32,
+    pub cost_usd: f64,
+    #[serde(default)]
+    pub input_cost_usd: f64,
+    #[serde(default)]
+    pub output_cost_usd: f64,
+    #[serde(default)]
+    pub batch_cost_usd: Option<f64>,          // Same call at the provider's batch API price
+    #[serde(default)]
+    pub cost_unknown: bool,                   // No pricing configured; cost_usd is 0 but not free
+    #[serde(default)]
+    pub cached_input_tokens: u32,
+    #[serde(default)]
+    pub cache_write_input_tokens: u32,        // Prompt tokens written to the provider's prompt cache
+    #[serde(default)]
+    pub token_count_source: TokenCountSource, // Least reliable source used for the token counts
+    pub timestamp: DateTime<Utc>,
+    pub error: Option<String>,
+    pub rate_limit_info: Option<RateLimitInfo>,
+    #[serde(default)]
+    pub time_to_first_token_ms: Option<u64>,  // Only recorded for streamed executions
+    #[serde(default)]
+    pub inter_token_latency_ms: Option<f64>,  // Mean gap between streamed chunks
+    #[serde(default)]
+    pub tokens_per_second: Option<f64>,       // Output tokens over the generation phase
+    #[serde(default)]
+    pub provider_metadata: HashMap<String, serde_json::Value>,  // Extra fields reported by the provider
+    #[serde(default)]
+    pub cache_hit: bool,                      // Served from the response cache; cost_usd was not paid again
+}
+
+// Where an execution's token counts came from, ordered from most to least reliable
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenCountSource {
+    #[default]
+    Provider,                                 // Usage reported in the API response
+    Tokenizer,                                // Counted with the model's configured tokenizer
+    Estimate,                                 // Character-based estimate (~4 chars per token)
+}
+
+// Extended EvaluationStatus with runs stopped by a signal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EvaluationStatus {
+    Configured,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,                                // Interrupted; holds the results that finished
+}
+
+// Extended ExecutionStatus with a distinct timeout outcome
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ExecutionStatus {
+    Success,
+    Failed,
+    Timeout,                                  // No response within the configured timeout
+}
+
+// Extended ResultSummary with timeouts counted apart from failures
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResultSummary {
+    pub total_executions: u32,
+    pub successful_executions: u32,
+    pub failed_executions: u32,
+    #[serde(default)]
+    pub timed_out_executions: u32,
+    #[serde(default)]
+    pub skipped_executions: u32,              // Not finished because the budget was reached or the run was cancelled
+    pub total_cost: f64,                      // Paid by this run; cache hits are left out
+    pub avg_response_time: f64,
+    pub success_rate: f32,
+    pub best_performing_model: Option<String>,
+    pub most_cost_effective: Option<String>,
+    pub fastest_model: Option<String>,
+}
+
+// Extended RateLimitInfo with client-side throttling and retries
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RateLimitInfo {
+    #[serde(default)]
+    pub throttle_wait_ms: u64,  // Time held back by the provider's rate limiter
+    #[serde(default)]
+    pub retry_attempts: u32,
+    #[serde(default)]
+    pub retry_wait_ms: u64,     // Total backoff between attempts
+    #[serde(default)]
+    pub retry_errors: Vec<String>,  // Error from each failed attempt, in order
+}
+
+// Extended CostBreakdown with unpriced models, batch pricing and cache savings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostBreakdown {
+    pub total_cost: f64,                      // Paid by this run; cache hits are left out
+    pub cost_by_provider: HashMap<String, f64>,
+    pub cost_by_model: HashMap<String, f64>,
+    pub input_token_cost: f64,
+    pub output_token_cost: f64,
+    #[serde(default)]
+    pub unpriced_models: Vec<String>,         // provider/model pairs with no configured pricing
+    #[serde(default)]
+    pub batch_cost_estimate: Option<f64>,     // Total at batch prices, for models that have a discount
+    #[serde(default)]
+    pub cache_savings: f64,                   // Cost of the results served from the response cache, not in total_cost
+}
+
+// Extended PerformanceStats with streaming latency aggregates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerformanceStats {
+    pub avg_response_time: f64,
+    pub median_response_time: f64,
+    pub min_response_time: u64,
+    pub max_response_time: u64,
+    pub success_rate: f32,
+    pub total_executions: u32,
+    pub failed_executions: u32,
+    #[serde(default)]
+    pub timed_out_executions: u32,
+    #[serde(default)]
+    pub timeouts_by_model: HashMap<String, u32>,
+    #[serde(default)]
+    pub avg_time_to_first_token: Option<f64>,
+    #[serde(default)]
+    pub median_time_to_first_token: Option<f64>,
+    #[serde(default)]
+    pub avg_inter_token_latency: Option<f64>,
+    #[serde(default)]
+    pub avg_tokens_per_second: Option<f64>,
+    #[serde(default)]
+    pub streaming_by_model: HashMap<String, StreamingStats>,
+}
+
+// Per-model streaming latency summary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamingStats {
+    pub streamed_executions: u32,
+    pub avg_time_to_first_token: f64,
+    pub avg_inter_token_latency: Option<f64>,
+    pub avg_tokens_per_second: Option<f64>,
+}
+
+// Extended ModelSettings with the full set of sampling controls
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelSettings {
+    pub temperature: Option<f32>,
+    pub max_tokens: Option<u32>,
+    #[serde(default)]
+    pub top_p: Option<f32>,
+    #[serde(default)]
+    pub top_k: Option<u32>,
+    #[serde(default)]
+    pub stop: Vec<String>,                    // Stop sequences
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(default)]
+    pub presence_penalty: Option<f32>,
+    #[serde(default)]
+    pub frequency_penalty: Option<f32>,
+    #[serde(default)]
+    pub extra: HashMap<String, serde_json::Value>,  // Merged into the provider's request body as-is
+}
+
+impl ModelSettings {
+    /// Names of the optional sampling fields that are set, for checking against what a provider sends
+    pub fn configured_fields(&self) -> Vec<&'static str> {
+        let fields = [
+            ("top_p", self.top_p.is_some()),
+            ("top_k", self.top_k.is_some()),
+            ("stop", !self.stop.is_empty()),
+            ("seed", self.seed.is_some()),
+            ("presence_penalty", self.presence_penalty.is_some()),
+            ("frequency_penalty", self.frequency_penalty.is_some()),
+        ];
+        
+        fields.iter()
+            .filter(|(_, set)| *set)
+            .map(|(name, _)| *name)
+            .collect()
+    }
+}
+
+// Extended BatchSettings with streaming execution, repeated sampling and a cost budget
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchSettings {
+    pub parallel_execution: bool,
+    pub max_concurrent: u32,
+    pub retry_attempts: u32,
+    pub timeout_seconds: u64,
+    #[serde(default)]
+    pub streaming: bool,                      // Stream responses to measure time-to-first-token
+    #[serde(default = "default_repetitions")]
+    pub repetitions: u32,                     // Samples per prompt x model cell
+    #[serde(default)]
+    pub max_cost_usd: Option<f64>,            // Refuse runs estimated above this and stop once it is spent
+}
+
+fn default_repetitions() -> u32 {
+    1
+}
+
+impl Default for BatchSettings {
+    fn default() -> Self {
+        Self {
+            parallel_execution: true,
+            max_concurrent: 5,
+            retry_attempts: 3,
+            timeout_seconds: 120,
+            streaming: false,
+            repetitions: 1,
+            max_cost_usd: None,
+        }
+    }
+}
+
 // Storage trait for evaluation management
 #[async_trait::async_trait]
 pub trait EvaluationStorage {
@@ -148,4 +768,42 @@ impl Evaluation {
             author: self.author.clone(),
         }
     }
+    
+    /// The evaluation as it was for one run: that run's config, status and results
+    pub fn at_run(mut self, run: &EvaluationRun) -> Self {
+        self.config = run.config.clone();
+        self.status = run.status.clone();
+        self.results = run.results.clone();
+        self.completed_at = run.completed_at;
+        self
+    }
+}
+
+impl EvaluationRun {
+    pub fn new(evaluation: &Evaluation, number: u32, environment: RunEnvironment) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            evaluation_id: evaluation.id.clone(),
+            number,
+            started_at: Utc::now(),
+            completed_at: None,
+            status: EvaluationStatus::Running,
+            config: evaluation.config.clone(),
+            environment,
+            results: None,
+        }
+    }
+}
+
+impl RunEnvironment {
+    pub fn current(credential_profile: Option<&str>, cassette: Option<&str>, response_cache: bool) -> Self {
+        Self {
+            evaleds_version: env!("CARGO_PKG_VERSION").to_string(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            credential_profile: credential_profile.map(str::to_string),
+            cassette: cassette.map(str::to_string),
+            response_cache,
+        }
+    }
 }
\ No newline at end of file
@@ -2,4 +2,12 @@ pub mod evaluation;
 pub mod evaluation_ext;
 pub mod storage;
 pub mod providers;
+pub mod streaming;
+pub mod cassette;
+pub mod rate_limiter;
+pub mod response_cache;
+pub mod pricing;
+pub mod tokenizer;
+pub mod catalog;
+pub mod schema;
 pub mod analysis;
\ No newline at end of file
@@ -1,5 +1,6 @@
 // Error handling for EvalEds - GNU-style error formatting aligned with PromptEds
 use thiserror::Error;
+use std::time::Duration;
 
 pub type Result<T> = std::result::Result<T, EvalError>;
 
@@ -43,6 +44,109 @@ pub enum EvalError {
     
     #[error("Permission denied: {0}")]
     PermissionDenied(String),
+    
+    #[error("Replay error: {0}")]
+    ReplayError(String),
+    
+    #[error("Budget exceeded: {0}")]
+    BudgetExceeded(String),
+    
+    #[error("Cancelled: {0}")]
+    Cancelled(String),
+    
+    #[error("API error: {0}")]
+    ApiError(ApiError),
+}
+
+/// Classified failure reported by a provider API
+#[derive(Debug, Clone)]
+pub struct ApiError {
+    pub kind: ApiErrorKind,
+    pub status: Option<u16>,
+    pub message: String,
+    pub retry_after: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiErrorKind {
+    Authentication,
+    RateLimited,
+    ServerError,
+    ContextLength,
+    ContentFilter,
+    InvalidRequest,
+    EmptyResponse,
+}
+
+impl ApiError {
+    pub fn new(kind: ApiErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            status: None,
+            message: message.into(),
+            retry_after: None,
+        }
+    }
+    
+    /// Classify a non-2xx response from its status code and error body
+    pub fn from_response(status: u16, body: &str, retry_after: Option<Duration>) -> Self {
+        let lower = body.to_lowercase();
+        
+        let kind = if lower.contains("context_length_exceeded")
+            || lower.contains("maximum context length")
+            || lower.contains("prompt is too long")
+            || lower.contains("too many tokens")
+            || lower.contains("exceeds the maximum number of tokens") {
+            ApiErrorKind::ContextLength
+        } else if lower.contains("content_filter")
+            || lower.contains("content_policy")
+            || lower.contains("content management policy") {
+            ApiErrorKind::ContentFilter
+        } else {
+            match status {
+                401 | 403 => ApiErrorKind::Authentication,
+                429 => ApiErrorKind::RateLimited,
+                500..=599 => ApiErrorKind::ServerError,
+                _ => ApiErrorKind::InvalidRequest,
+            }
+        };
+        
+        Self {
+            kind,
+            status: Some(status),
+            message: body.trim().to_string(),
+            retry_after,
+        }
+    }
+    
+    /// Transient failures worth retrying with backoff
+    pub fn is_retryable(&self) -> bool {
+        matches!(self.kind, ApiErrorKind::RateLimited | ApiErrorKind::ServerError)
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.status {
+            Some(status) => write!(f, "{} (HTTP {}): {}", self.kind, status, self.message),
+            None => write!(f, "{}: {}", self.kind, self.message),
+        }
+    }
+}
+
+impl std::fmt::Display for ApiErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            ApiErrorKind::Authentication => "authentication failed",
+            ApiErrorKind::RateLimited => "rate limited",
+            ApiErrorKind::ServerError => "server error",
+            ApiErrorKind::ContextLength => "context length exceeded",
+            ApiErrorKind::ContentFilter => "blocked by content filter",
+            ApiErrorKind::InvalidRequest => "invalid request",
+            ApiErrorKind::EmptyResponse => "empty response",
+        };
+        write!(f, "{}", label)
+    }
 }
 
 impl EvalError {
@@ -88,6 +192,18 @@ impl EvalError {
             EvalError::AnalysisError(msg) => {
                 format!("evaleds: {}: analysis error: {}", command, msg)
             },
+            EvalError::ReplayError(msg) => {
+                format!("evaleds: {}: replay error: {}", command, msg)
+            },
+            EvalError::BudgetExceeded(msg) => {
+                format!("evaleds: {}: budget exceeded: {}", command, msg)
+            },
+            EvalError::Cancelled(msg) => {
+                format!("evaleds: {}: cancelled: {}", command, msg)
+            },
+            EvalError::ApiError(err) => {
+                format!("evaleds: {}: provider error: {}", command, err)
+            },
         }
     }
     
@@ -107,6 +223,10 @@ impl EvalError {
             EvalError::SerializationError(_) => 10,
             EvalError::TemplateError(_) => 11,
             EvalError::AnalysisError(_) => 12,
+            EvalError::ReplayError(_) => 13,
+            EvalError::BudgetExceeded(_) => 14,
+            EvalError::Cancelled(_) => 130,
+            EvalError::ApiError(_) => 4,
         }
     }
     
@@ -122,6 +242,27 @@ impl EvalError {
             EvalError::ConfigError(_) => Some(
                 "💡 Check your configuration files:\n   - ~/.config/evaleds/config.toml\n   - ~/.evaleds/config.toml".to_string()
             ),
+            EvalError::ReplayError(_) => Some(
+                "💡 The cassette has no recording for this request\n💡 Re-record it with 'evaleds run <name> --force --record <dir>'".to_string()
+            ),
+            EvalError::BudgetExceeded(_) => Some(
+                "💡 Raise the budget with --budget <usd> or max_cost_usd in the batch settings\n💡 Or pass --yes to start anyway; the run still stops once the budget is spent".to_string()
+            ),
+            EvalError::Cancelled(_) => Some(
+                "💡 Continue with 'evaleds run <name> --resume'".to_string()
+            ),
+            EvalError::ApiError(err) => match err.kind {
+                ApiErrorKind::Authentication => Some(
+                    "💡 Check the API key for this provider is set and still valid".to_string()
+                ),
+                ApiErrorKind::RateLimited => Some(
+                    "💡 Lower requests_per_minute / tokens_per_minute for this provider\n💡 Or reduce --max-concurrent".to_string()
+                ),
+                ApiErrorKind::ContextLength => Some(
+                    "💡 Shorten the prompt or lower max_tokens for this model".to_string()
+                ),
+                _ => None,
+            },
             EvalError::MissingDependency(_) => Some(
                 "💡 Make sure all required dependencies are installed\n💡 Try running 'evaleds --version' to check installation".to_string()
             ),
@@ -164,4 +305,61 @@ impl From<dialoguer::Error> for EvalError {
     fn from(err: dialoguer::Error) -> Self {
         EvalError::ValidationError(err.to_string())
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn rate_limit_keeps_retry_after() {
+        let error = ApiError::from_response(429, "Too Many Requests", Some(Duration::from_secs(7)));
+        assert_eq!(error.kind, ApiErrorKind::RateLimited);
+        assert_eq!(error.status, Some(429));
+        assert_eq!(error.retry_after, Some(Duration::from_secs(7)));
+        assert!(error.is_retryable());
+    }
+    
+    #[test]
+    fn status_codes_are_classified() {
+        assert_eq!(ApiError::from_response(401, "", None).kind, ApiErrorKind::Authentication);
+        assert_eq!(ApiError::from_response(403, "", None).kind, ApiErrorKind::Authentication);
+        assert_eq!(ApiError::from_response(500, "", None).kind, ApiErrorKind::ServerError);
+        assert_eq!(ApiError::from_response(529, "overloaded", None).kind, ApiErrorKind::ServerError);
+        assert_eq!(ApiError::from_response(400, "bad field", None).kind, ApiErrorKind::InvalidRequest);
+        assert_eq!(ApiError::from_response(404, "", None).kind, ApiErrorKind::InvalidRequest);
+    }
+    
+    #[test]
+    fn body_markers_take_precedence_over_status() {
+        let context = ApiError::from_response(400, r#"{"error":{"code":"context_length_exceeded"}}"#, None);
+        assert_eq!(context.kind, ApiErrorKind::ContextLength);
+        
+        let prompt = ApiError::from_response(400, "Prompt is too long: 210000 tokens", None);
+        assert_eq!(prompt.kind, ApiErrorKind::ContextLength);
+        
+        let filtered = ApiError::from_response(400, r#"{"error":{"code":"content_filter"}}"#, None);
+        assert_eq!(filtered.kind, ApiErrorKind::ContentFilter);
+        
+        // A server error whose body mentions the context limit is not worth retrying
+        let server = ApiError::from_response(500, "exceeds the maximum number of tokens", None);
+        assert_eq!(server.kind, ApiErrorKind::ContextLength);
+        assert!(!server.is_retryable());
+    }
+    
+    #[test]
+    fn only_transient_failures_are_retryable() {
+        assert!(ApiError::new(ApiErrorKind::ServerError, "").is_retryable());
+        assert!(!ApiError::new(ApiErrorKind::Authentication, "").is_retryable());
+        assert!(!ApiError::new(ApiErrorKind::InvalidRequest, "").is_retryable());
+        assert!(!ApiError::new(ApiErrorKind::EmptyResponse, "").is_retryable());
+    }
+    
+    #[test]
+    fn message_is_trimmed_and_displayed_with_status() {
+        let error = ApiError::from_response(503, "  unavailable\n", None);
+        assert_eq!(error.message, "unavailable");
+        assert_eq!(error.to_string(), "server error (HTTP 503): unavailable");
+        assert_eq!(ApiError::new(ApiErrorKind::EmptyResponse, "no text").to_string(), "empty response: no text");
+    }
 }
\ No newline at end of file
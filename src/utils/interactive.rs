@@ -1,5 +1,5 @@
 // Interactive prompting style aligned with PromptEds patterns
-use dialoguer::{Input, Select, MultiSelect, Confirm, theme::ColorfulTheme};
+use dialoguer::{Input, Password, Select, MultiSelect, Confirm, theme::ColorfulTheme};
 use console::{style, Term};
 use crate::utils::error::Result;
 use std::collections::HashMap;
@@ -86,6 +86,13 @@ pub fn prompt_text(label: &str, default: Option<&str>, required: bool) -> Result
     Ok(input.interact_text()?)
 }
 
+/// Prompt for a secret without echoing it
+pub fn prompt_password(label: &str) -> Result<String> {
+    Ok(Password::with_theme(&get_prompteds_theme())
+        .with_prompt(format!("🔑 {}", label))
+        .interact()?)
+}
+
 /// Prompt for selection with PromptEds-style formatting
 pub fn prompt_select<T: ToString>(label: &str, items: &[T], default: Option<usize>) -> Result<usize> {
     let mut select = Select::with_theme(&get_prompteds_theme())
@@ -1,4 +1,5 @@
 pub mod error;
 pub mod config;
+pub mod credentials;
 pub mod interactive;
 pub mod output;
\ No newline at end of file
@@ -1,6 +1,6 @@
 // Output formatting and color scheme aligned with PromptEds
 use console::{style, Term, Color};
-use crate::core::evaluation::{Evaluation, EvaluationStatus, EvaluationSummary};
+use crate::core::evaluation::{Evaluation, EvaluationRun, EvaluationStatus, EvaluationSummary};
 use crate::core::storage::StorageStats;
 use crate::utils::error::Result;
 use chrono::{DateTime, Utc};
@@ -24,6 +24,7 @@ impl PromptEdsColors {
     pub fn status_running() -> console::Style { style("").yellow() }
     pub fn status_failed() -> console::Style { style("").red() }
     pub fn status_configured() -> console::Style { style("").blue() }
+    pub fn status_cancelled() -> console::Style { style("").magenta() }
     
     // Accent colors
     pub fn tag() -> console::Style { style("").green() }
@@ -58,6 +59,7 @@ pub fn format_evaluation_status(status: &EvaluationStatus, use_color: bool) -> S
         EvaluationStatus::Running => ("🔄", "running", PromptEdsColors::status_running),
         EvaluationStatus::Failed => ("❌", "failed", PromptEdsColors::status_failed),
         EvaluationStatus::Configured => ("⚙️", "configured", PromptEdsColors::status_configured),
+        EvaluationStatus::Cancelled => ("⏹️", "cancelled", PromptEdsColors::status_cancelled),
     };
     
     if use_color {
@@ -172,6 +174,60 @@ pub fn display_simple_list(evaluations: &[EvaluationSummary], use_color: bool) -
     Ok(())
 }
 
+/// Display the run history of one evaluation, oldest first
+pub fn display_run_list(runs: &[EvaluationRun], use_color: bool) -> Result<()> {
+    for run in runs {
+        let number = format!("#{}", run.number);
+        if use_color {
+            print!("{}", PromptEdsColors::bold().apply_to(&number));
+        } else {
+            print!("{}", number);
+        }
+        print!(" {}", format_evaluation_status(&run.status, use_color));
+        print!(" {}", format_timestamp(&run.started_at, use_color));
+        
+        if let Some(results) = &run.results {
+            let summary = &results.summary;
+            print!(" [{} executions, {:.1}% success, {}]",
+                   summary.total_executions, summary.success_rate, format_cost(summary.total_cost, use_color));
+        }
+        
+        // What the run was executed with, when it is known
+        let mut environment = Vec::new();
+        if !run.environment.evaleds_version.is_empty() {
+            environment.push(format!("v{}", run.environment.evaleds_version));
+        }
+        if let Some(profile) = &run.environment.credential_profile {
+            environment.push(format!("profile {}", profile));
+        }
+        if let Some(cassette) = &run.environment.cassette {
+            environment.push(format!("cassette {}", cassette));
+        }
+        if run.environment.response_cache {
+            environment.push("response cache".to_string());
+        }
+        if !environment.is_empty() {
+            let environment_str = environment.join(", ");
+            if use_color {
+                print!(" {}", PromptEdsColors::dim().apply_to(&environment_str));
+            } else {
+                print!(" {}", environment_str);
+            }
+        }
+        
+        println!();
+    }
+    
+    let count_str = format!("{} run{}", runs.len(), if runs.len() == 1 { "" } else { "s" });
+    if use_color {
+        println!("\n{}", PromptEdsColors::bold().apply_to(&count_str));
+    } else {
+        println!("\n{}", count_str);
+    }
+    
+    Ok(())
+}
+
 /// Display detailed evaluation list (PromptEds style)
 pub fn display_detailed_list(evaluations: &[EvaluationSummary], use_color: bool) -> Result<()> {
     for (i, evaluation) in evaluations.iter().enumerate() {
@@ -325,6 +381,7 @@ pub fn display_results_summary(evaluation: &Evaluation, use_color: bool) -> Resu
             ("Total Executions", results.summary.total_executions.to_string()),
             ("Successful", results.summary.successful_executions.to_string()),
             ("Failed", results.summary.failed_executions.to_string()),
+            ("Timed Out", results.summary.timed_out_executions.to_string()),
             ("Success Rate", format!("{:.1}%", results.summary.success_rate)),
             ("Total Cost", format!("${:.4}", results.summary.total_cost)),
             ("Avg Response Time", format!("{:.0}ms", results.summary.avg_response_time)),
@@ -77,6 +77,55 @@ impl Storage {
         .await
         .map_err(|e| EvalError::DatabaseError(e.to_string()))?;
         
+        // Create runs table; each run keeps its own config snapshot and its summary and
+        // analysis. Its executions are rows in execution_results.
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS runs (
+                id TEXT PRIMARY KEY,
+                evaluation_id TEXT NOT NULL,
+                number INTEGER NOT NULL,
+                started_at TEXT NOT NULL,
+                completed_at TEXT,
+                status TEXT NOT NULL,
+                config TEXT NOT NULL,
+                environment TEXT NOT NULL,
+                results TEXT,
+                UNIQUE (evaluation_id, number),
+                FOREIGN KEY (evaluation_id) REFERENCES evaluations (id)
+            )
+        "#)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| EvalError::DatabaseError(e.to_string()))?;
+        
+        // Columns added after the first release
+        self.ensure_column("execution_results", "cell_id", "TEXT").await?;
+        self.ensure_column("execution_results", "repetition", "INTEGER NOT NULL DEFAULT 0").await?;
+        self.ensure_column("execution_results", "result", "TEXT").await?;
+        self.ensure_column("execution_results", "run_id", "TEXT").await?;
+        self.ensure_column("execution_results", "prompt_index", "INTEGER").await?;
+        self.ensure_column("execution_results", "variation", "INTEGER").await?;
+        self.ensure_column("execution_results", "variation_name", "TEXT").await?;
+        self.ensure_column("execution_results", "prompt_hash", "TEXT").await?;
+        
+        // Results stored before run history become each evaluation's first run, reusing
+        // the evaluation id as the run id
+        sqlx::query(r#"
+            INSERT INTO runs (id, evaluation_id, number, started_at, completed_at, status, config, environment, results)
+            SELECT id, id, 1, created_at, completed_at, status, config, '{}', results
+            FROM evaluations
+            WHERE (results IS NOT NULL OR EXISTS (SELECT 1 FROM execution_results WHERE evaluation_id = evaluations.id))
+              AND NOT EXISTS (SELECT 1 FROM runs WHERE evaluation_id = evaluations.id)
+        "#)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| EvalError::DatabaseError(e.to_string()))?;
+        
+        sqlx::query("UPDATE execution_results SET run_id = evaluation_id WHERE run_id IS NULL")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| EvalError::DatabaseError(e.to_string()))?;
+        
         // Create indexes for better performance
         sqlx::query("CREATE INDEX IF NOT EXISTS idx_evaluations_name ON evaluations (name)")
             .execute(&self.pool)
@@ -93,20 +142,44 @@ impl Storage {
             .await
             .map_err(|e| EvalError::DatabaseError(e.to_string()))?;
         
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_execution_results_run_id ON execution_results (run_id)")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| EvalError::DatabaseError(e.to_string()))?;
+        
+        // Joins the same cell across runs
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_execution_results_cell_id ON execution_results (cell_id)")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| EvalError::DatabaseError(e.to_string()))?;
+        
+        Ok(())
+    }
+    
+    /// Add a column to an existing table unless it is already there
+    async fn ensure_column(&self, table: &str, column: &str, definition: &str) -> Result<()> {
+        let columns = sqlx::query(&format!("PRAGMA table_info({})", table))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| EvalError::DatabaseError(e.to_string()))?;
+        
+        if columns.iter().any(|row| row.get::<String, _>("name") == column) {
+            return Ok(());
+        }
+        
+        sqlx::query(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition))
+            .execute(&self.pool)
+            .await
+            .map_err(|e| EvalError::DatabaseError(e.to_string()))?;
+        
         Ok(())
     }
     
+    /// The evaluation's results are stored with its runs, not here
     pub async fn save_evaluation(&self, evaluation: &Evaluation) -> Result<()> {
         let config_json = serde_json::to_string(&evaluation.config)
             .map_err(|e| EvalError::SerializationError(e.to_string()))?;
         
-        let results_json = if let Some(results) = &evaluation.results {
-            Some(serde_json::to_string(results)
-                .map_err(|e| EvalError::SerializationError(e.to_string()))?)
-        } else {
-            None
-        };
-        
         sqlx::query(r#"
             INSERT OR REPLACE INTO evaluations 
             (id, name, description, config, results, created_at, completed_at, status)
@@ -116,7 +189,7 @@ impl Storage {
         .bind(&evaluation.name)
         .bind(&evaluation.description)
         .bind(&config_json)
-        .bind(&results_json)
+        .bind(None::<String>)
         .bind(evaluation.created_at.to_rfc3339())
         .bind(evaluation.completed_at.map(|dt| dt.to_rfc3339()))
         .bind(serde_json::to_string(&evaluation.status).unwrap())
@@ -124,17 +197,13 @@ impl Storage {
         .await
         .map_err(|e| EvalError::DatabaseError(e.to_string()))?;
         
-        // Save execution results separately for better querying
-        if let Some(results) = &evaluation.results {
-            self.save_execution_results(&evaluation.id, &results.executions).await?;
-        }
-        
         Ok(())
     }
     
+    /// Results come from the latest run
     pub async fn load_evaluation(&self, name: &str) -> Result<Option<Evaluation>> {
         let row = sqlx::query(r#"
-            SELECT id, name, description, config, results, created_at, completed_at, status
+            SELECT id, name, description, config, created_at, completed_at, status
             FROM evaluations 
             WHERE name = ?
         "#)
@@ -147,12 +216,8 @@ impl Storage {
             let config: EvaluationConfig = serde_json::from_str(row.get("config"))
                 .map_err(|e| EvalError::SerializationError(e.to_string()))?;
             
-            let results: Option<EvaluationResults> = if let Some(results_str): Option<String> = row.get("results") {
-                Some(serde_json::from_str(&results_str)
-                    .map_err(|e| EvalError::SerializationError(e.to_string()))?)
-            } else {
-                None
-            };
+            let id: String = row.get("id");
+            let results = self.load_run(&id, None).await?.and_then(|run| run.results);
             
             let created_at = chrono::DateTime::parse_from_rfc3339(row.get("created_at"))
                 .map_err(|e| EvalError::SerializationError(e.to_string()))?
@@ -170,7 +235,7 @@ impl Storage {
                 .map_err(|e| EvalError::SerializationError(e.to_string()))?;
             
             Ok(Some(Evaluation {
-                id: row.get("id"),
+                id,
                 name: row.get("name"),
                 description: row.get("description"),
                 config,
@@ -191,7 +256,8 @@ impl Storage {
     pub async fn list_evaluations(&self) -> Result<Vec<EvaluationSummary>> {
         let rows = sqlx::query(r#"
             SELECT id, name, description, created_at, completed_at, status,
-                   (SELECT COUNT(*) FROM execution_results WHERE evaluation_id = evaluations.id) as execution_count
+                   (SELECT COUNT(*) FROM execution_results WHERE run_id =
+                       (SELECT id FROM runs WHERE evaluation_id = evaluations.id ORDER BY number DESC LIMIT 1)) as execution_count
             FROM evaluations 
             ORDER BY created_at DESC
         "#)
@@ -241,13 +307,19 @@ impl Storage {
             .map(|row| row.get("id"));
         
         if let Some(id) = evaluation_id {
-            // Delete execution results first (foreign key constraint)
+            // Delete execution results and runs first (foreign key constraint)
             sqlx::query("DELETE FROM execution_results WHERE evaluation_id = ?")
                 .bind(&id)
                 .execute(&self.pool)
                 .await
                 .map_err(|e| EvalError::DatabaseError(e.to_string()))?;
             
+            sqlx::query("DELETE FROM runs WHERE evaluation_id = ?")
+                .bind(&id)
+                .execute(&self.pool)
+                .await
+                .map_err(|e| EvalError::DatabaseError(e.to_string()))?;
+            
             // Delete the evaluation
             let result = sqlx::query("DELETE FROM evaluations WHERE id = ?")
                 .bind(&id)
@@ -261,44 +333,215 @@ impl Storage {
         }
     }
     
-    async fn save_execution_results(&self, evaluation_id: &str, results: &[ExecutionResult]) -> Result<()> {
-        // Delete existing results for this evaluation
-        sqlx::query("DELETE FROM execution_results WHERE evaluation_id = ?")
+    /// Save a run's config, environment, summary and analysis. Its executions are not
+    /// written here: each one is saved with `save_execution_result` as it completes.
+    pub async fn save_run(&self, run: &EvaluationRun) -> Result<()> {
+        let config_json = serde_json::to_string(&run.config)
+            .map_err(|e| EvalError::SerializationError(e.to_string()))?;
+        let environment_json = serde_json::to_string(&run.environment)
+            .map_err(|e| EvalError::SerializationError(e.to_string()))?;
+        
+        let results_json = if let Some(results) = &run.results {
+            let without_executions = EvaluationResults {
+                executions: Vec::new(),
+                analysis: results.analysis.clone(),
+                summary: results.summary.clone(),
+                report_path: results.report_path.clone(),
+            };
+            Some(serde_json::to_string(&without_executions)
+                .map_err(|e| EvalError::SerializationError(e.to_string()))?)
+        } else {
+            None
+        };
+        
+        sqlx::query(r#"
+            INSERT OR REPLACE INTO runs
+            (id, evaluation_id, number, started_at, completed_at, status, config, environment, results)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#)
+        .bind(&run.id)
+        .bind(&run.evaluation_id)
+        .bind(run.number as i64)
+        .bind(run.started_at.to_rfc3339())
+        .bind(run.completed_at.map(|dt| dt.to_rfc3339()))
+        .bind(serde_json::to_string(&run.status).unwrap())
+        .bind(&config_json)
+        .bind(&environment_json)
+        .bind(&results_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| EvalError::DatabaseError(e.to_string()))?;
+        
+        Ok(())
+    }
+    
+    /// Every run of an evaluation, oldest first
+    pub async fn list_runs(&self, evaluation_id: &str) -> Result<Vec<EvaluationRun>> {
+        let rows = sqlx::query(r#"
+            SELECT id, evaluation_id, number, started_at, completed_at, status, config, environment, results
+            FROM runs
+            WHERE evaluation_id = ?
+            ORDER BY number
+        "#)
+        .bind(evaluation_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| EvalError::DatabaseError(e.to_string()))?;
+        
+        let mut runs = Vec::with_capacity(rows.len());
+        for row in &rows {
+            runs.push(self.with_executions(run_from_row(row)?).await?);
+        }
+        Ok(runs)
+    }
+    
+    /// One run by number, or the latest when no number is given
+    pub async fn load_run(&self, evaluation_id: &str, number: Option<u32>) -> Result<Option<EvaluationRun>> {
+        let row = sqlx::query(r#"
+            SELECT id, evaluation_id, number, started_at, completed_at, status, config, environment, results
+            FROM runs
+            WHERE evaluation_id = ? AND (? IS NULL OR number = ?)
+            ORDER BY number DESC
+            LIMIT 1
+        "#)
+        .bind(evaluation_id)
+        .bind(number.map(|n| n as i64))
+        .bind(number.map(|n| n as i64))
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| EvalError::DatabaseError(e.to_string()))?;
+        
+        match row {
+            Some(row) => Ok(Some(self.with_executions(run_from_row(&row)?).await?)),
+            None => Ok(None),
+        }
+    }
+    
+    /// Fill in a finished run's executions from execution_results. Runs saved before
+    /// executions moved out of the runs table still carry them inline and are kept as-is.
+    async fn with_executions(&self, mut run: EvaluationRun) -> Result<EvaluationRun> {
+        if let Some(results) = &mut run.results {
+            if results.executions.is_empty() {
+                results.executions = self.load_execution_results(&run.id).await?;
+            }
+        }
+        Ok(run)
+    }
+    
+    pub async fn next_run_number(&self, evaluation_id: &str) -> Result<u32> {
+        let row = sqlx::query("SELECT MAX(number) as last FROM runs WHERE evaluation_id = ?")
             .bind(evaluation_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| EvalError::DatabaseError(e.to_string()))?;
+        
+        Ok(row.get::<Option<i64>, _>("last").unwrap_or(0) as u32 + 1)
+    }
+    
+    /// Drop all but the `keep` most recent runs, returning how many were removed
+    pub async fn prune_runs(&self, evaluation_id: &str, keep: u32) -> Result<u32> {
+        let stale = r#"
+            SELECT id FROM runs WHERE evaluation_id = ?
+            ORDER BY number DESC
+            LIMIT -1 OFFSET ?
+        "#;
+        
+        sqlx::query(&format!("DELETE FROM execution_results WHERE run_id IN ({})", stale))
+            .bind(evaluation_id)
+            .bind(keep as i64)
             .execute(&self.pool)
             .await
             .map_err(|e| EvalError::DatabaseError(e.to_string()))?;
         
-        // Insert new results
-        for result in results {
-            sqlx::query(r#"
-                INSERT INTO execution_results 
-                (id, evaluation_id, prompt_id, provider, model, input, output, status,
-                 response_time_ms, token_count_input, token_count_output, cost_usd, timestamp, error)
-                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-            "#)
-            .bind(&result.id)
+        let result = sqlx::query(&format!("DELETE FROM runs WHERE id IN ({})", stale))
             .bind(evaluation_id)
-            .bind(&result.prompt_id)
-            .bind(&result.provider)
-            .bind(&result.model)
-            .bind(&result.input)
-            .bind(&result.output)
-            .bind(serde_json::to_string(&result.status).unwrap())
-            .bind(result.metadata.response_time_ms as i64)
-            .bind(result.metadata.token_count_input as i64)
-            .bind(result.metadata.token_count_output as i64)
-            .bind(result.metadata.cost_usd)
-            .bind(result.metadata.timestamp.to_rfc3339())
-            .bind(&result.metadata.error)
+            .bind(keep as i64)
             .execute(&self.pool)
             .await
             .map_err(|e| EvalError::DatabaseError(e.to_string()))?;
-        }
+        
+        Ok(result.rows_affected() as u32)
+    }
+    
+    /// Persist one result as soon as it completes, so an interrupted run keeps what it paid for.
+    /// The full result is stored as JSON alongside the queryable columns, which include the
+    /// prompt and variation it was rendered from.
+    pub async fn save_execution_result(&self, evaluation_id: &str, run_id: &str, result: &ExecutionResult) -> Result<()> {
+        let result_json = serde_json::to_string(result)
+            .map_err(|e| EvalError::SerializationError(e.to_string()))?;
+        let resolved_prompt = result.resolved_prompt.as_ref();
+        
+        sqlx::query(r#"
+            INSERT OR REPLACE INTO execution_results 
+            (id, evaluation_id, prompt_id, provider, model, input, output, status,
+             response_time_ms, token_count_input, token_count_output, cost_usd, timestamp, error,
+             cell_id, repetition, result, run_id, prompt_index, variation, variation_name, prompt_hash)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#)
+        .bind(&result.id)
+        .bind(evaluation_id)
+        .bind(&result.prompt_id)
+        .bind(&result.provider)
+        .bind(&result.model)
+        .bind(&result.input)
+        .bind(&result.output)
+        .bind(serde_json::to_string(&result.status).unwrap())
+        .bind(result.metadata.response_time_ms as i64)
+        .bind(result.metadata.token_count_input as i64)
+        .bind(result.metadata.token_count_output as i64)
+        .bind(result.metadata.cost_usd)
+        .bind(result.metadata.timestamp.to_rfc3339())
+        .bind(&result.metadata.error)
+        .bind(&result.cell_id)
+        .bind(result.repetition as i64)
+        .bind(&result_json)
+        .bind(run_id)
+        .bind(resolved_prompt.map(|p| p.prompt_index as i64))
+        .bind(resolved_prompt.and_then(|p| p.variation).map(|v| v as i64))
+        .bind(resolved_prompt.and_then(|p| p.variation_name.as_deref()))
+        .bind(resolved_prompt.map(|p| p.content_hash.as_str()))
+        .execute(&self.pool)
+        .await
+        .map_err(|e| EvalError::DatabaseError(e.to_string()))?;
         
         Ok(())
     }
     
+    /// Drop a run's results other than `kept`, so a resumed run replaces failed and stale
+    /// results instead of adding to them
+    pub async fn retain_results(&self, run_id: &str, kept: &[String]) -> Result<()> {
+        let kept = serde_json::to_string(kept)
+            .map_err(|e| EvalError::SerializationError(e.to_string()))?;
+        
+        sqlx::query("DELETE FROM execution_results WHERE run_id = ? AND id NOT IN (SELECT value FROM json_each(?))")
+            .bind(run_id)
+            .bind(kept)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| EvalError::DatabaseError(e.to_string()))?;
+        
+        Ok(())
+    }
+    
+    /// Results persisted for a run, in completion order. Rows written before full
+    /// results were stored can't be rebuilt and are left out.
+    pub async fn load_execution_results(&self, run_id: &str) -> Result<Vec<ExecutionResult>> {
+        let rows = sqlx::query(r#"
+            SELECT result FROM execution_results
+            WHERE run_id = ? AND result IS NOT NULL
+            ORDER BY timestamp
+        "#)
+        .bind(run_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| EvalError::DatabaseError(e.to_string()))?;
+        
+        rows.iter()
+            .map(|row| serde_json::from_str(row.get("result"))
+                .map_err(|e| EvalError::SerializationError(e.to_string())))
+            .collect()
+    }
+    
     pub async fn get_evaluation_stats(&self) -> Result<StorageStats> {
         let stats_row = sqlx::query(r#"
             SELECT 
@@ -306,7 +549,8 @@ impl Storage {
                 COUNT(CASE WHEN status = '"Completed"' THEN 1 END) as completed_evaluations,
                 COUNT(CASE WHEN status = '"Running"' THEN 1 END) as running_evaluations,
                 (SELECT COUNT(*) FROM execution_results) as total_executions,
-                (SELECT SUM(cost_usd) FROM execution_results) as total_cost
+                (SELECT SUM(CASE WHEN json_extract(result, '$.metadata.cache_hit') = 1 THEN 0 ELSE cost_usd END)
+                    FROM execution_results) as total_cost
             FROM evaluations
         "#)
         .fetch_one(&self.pool)
@@ -325,7 +569,8 @@ impl Storage {
     pub async fn search_evaluations(&self, query: &str) -> Result<Vec<EvaluationSummary>> {
         let rows = sqlx::query(r#"
             SELECT id, name, description, created_at, completed_at, status,
-                   (SELECT COUNT(*) FROM execution_results WHERE evaluation_id = evaluations.id) as execution_count
+                   (SELECT COUNT(*) FROM execution_results WHERE run_id =
+                       (SELECT id FROM runs WHERE evaluation_id = evaluations.id ORDER BY number DESC LIMIT 1)) as execution_count
             FROM evaluations 
             WHERE name LIKE ? OR description LIKE ?
             ORDER BY created_at DESC
@@ -369,6 +614,33 @@ impl Storage {
     }
 }
 
+fn run_from_row(row: &sqlx::sqlite::SqliteRow) -> Result<EvaluationRun> {
+    let parse_timestamp = |value: &str| chrono::DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&chrono::Utc))
+        .map_err(|e| EvalError::SerializationError(e.to_string()));
+    
+    let results: Option<EvaluationResults> = match row.get::<Option<String>, _>("results") {
+        Some(results_str) => Some(serde_json::from_str(&results_str)
+            .map_err(|e| EvalError::SerializationError(e.to_string()))?),
+        None => None,
+    };
+    
+    Ok(EvaluationRun {
+        id: row.get("id"),
+        evaluation_id: row.get("evaluation_id"),
+        number: row.get::<i64, _>("number") as u32,
+        started_at: parse_timestamp(row.get("started_at"))?,
+        completed_at: row.get::<Option<String>, _>("completed_at").as_deref().map(parse_timestamp).transpose()?,
+        status: serde_json::from_str(row.get("status"))
+            .map_err(|e| EvalError::SerializationError(e.to_string()))?,
+        config: serde_json::from_str(row.get("config"))
+            .map_err(|e| EvalError::SerializationError(e.to_string()))?,
+        environment: serde_json::from_str(row.get("environment"))
+            .map_err(|e| EvalError::SerializationError(e.to_string()))?,
+        results,
+    })
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct EvaluationSummary {
     pub id: String,
@@ -0,0 +1,197 @@
+// Streaming response helpers shared by the provider implementations
+use crate::core::cassette::HttpResponse;
+use crate::utils::error::Result;
+use std::time::Instant;
+
+/// Token timing captured while consuming a streamed response
+#[derive(Debug, Clone, Default)]
+pub struct StreamTiming {
+    pub time_to_first_token_ms: u64,
+    pub inter_token_latencies_ms: Vec<f64>,
+}
+
+impl StreamTiming {
+    pub fn avg_inter_token_latency_ms(&self) -> Option<f64> {
+        if self.inter_token_latencies_ms.is_empty() {
+            None
+        } else {
+            Some(self.inter_token_latencies_ms.iter().sum::<f64>() / self.inter_token_latencies_ms.len() as f64)
+        }
+    }
+    
+    /// Throughput over the generation phase only, from the first token to the end of the response.
+    /// `None` when there was no output or no time between the first token and the end.
+    pub fn tokens_per_second(&self, output_tokens: u32, response_time_ms: u64) -> Option<f64> {
+        let generation_ms = response_time_ms.saturating_sub(self.time_to_first_token_ms);
+        if output_tokens == 0 || generation_ms == 0 {
+            return None;
+        }
+        Some(output_tokens as f64 / (generation_ms as f64 / 1000.0))
+    }
+}
+
+/// Records when content chunks arrive relative to the request start
+pub struct StreamTimer {
+    start: Instant,
+    first_token: Option<Instant>,
+    last_token: Option<Instant>,
+    intervals_ms: Vec<f64>,
+}
+
+impl StreamTimer {
+    pub fn start() -> Self {
+        Self {
+            start: Instant::now(),
+            first_token: None,
+            last_token: None,
+            intervals_ms: Vec::new(),
+        }
+    }
+    
+    /// Call once for every chunk that carries generated text
+    pub fn record_token(&mut self) {
+        let now = Instant::now();
+        
+        if let Some(last) = self.last_token {
+            self.intervals_ms.push(now.duration_since(last).as_secs_f64() * 1000.0);
+        } else {
+            self.first_token = Some(now);
+        }
+        
+        self.last_token = Some(now);
+    }
+    
+    /// Returns `None` when the stream never produced any text
+    pub fn finish(self) -> Option<StreamTiming> {
+        self.first_token.map(|first| StreamTiming {
+            time_to_first_token_ms: first.duration_since(self.start).as_millis() as u64,
+            inter_token_latencies_ms: self.intervals_ms,
+        })
+    }
+}
+
+/// Reads a chunked HTTP body line by line (SSE and NDJSON)
+pub struct LineReader {
+    response: HttpResponse,
+    buffer: Vec<u8>,
+    done: bool,
+}
+
+impl LineReader {
+    pub fn new(response: HttpResponse) -> Self {
+        Self {
+            response,
+            buffer: Vec::new(),
+            done: false,
+        }
+    }
+    
+    pub async fn next_line(&mut self) -> Result<Option<String>> {
+        loop {
+            if let Some(pos) = self.buffer.iter().position(|b| *b == b'\n') {
+                let line: Vec<u8> = self.buffer.drain(..=pos).collect();
+                return Ok(Some(decode_line(&line)));
+            }
+            
+            if self.done {
+                if self.buffer.is_empty() {
+                    return Ok(None);
+                }
+                let line = std::mem::take(&mut self.buffer);
+                return Ok(Some(decode_line(&line)));
+            }
+            
+            match self.response.chunk().await? {
+                Some(chunk) => self.buffer.extend_from_slice(&chunk),
+                None => self.done = true,
+            }
+        }
+    }
+}
+
+fn decode_line(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes)
+        .trim_end_matches(|c| c == '\r' || c == '\n')
+        .to_string()
+}
+
+/// Extract the payload of an SSE `data:` line, ignoring comments and event names
+pub fn sse_data(line: &str) -> Option<&str> {
+    line.strip_prefix("data:").map(|data| data.trim_start())
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    async fn read_lines(chunks: &[&str]) -> Vec<String> {
+        let chunks = chunks.iter().map(|chunk| chunk.as_bytes().to_vec()).collect();
+        let mut reader = LineReader::new(HttpResponse::chunked(200, chunks));
+        let mut lines = Vec::new();
+        while let Some(line) = reader.next_line().await.unwrap() {
+            lines.push(line);
+        }
+        lines
+    }
+    
+    #[tokio::test]
+    async fn sse_data_lines_split_across_chunks_are_joined() {
+        let lines = read_lines(&["data: {\"delta\":", " \"Hel\"}\r\n\nda", "ta: {\"delta\": \"lo\"}\n\n"]).await;
+        let data: Vec<&str> = lines.iter().filter_map(|line| sse_data(line)).collect();
+        assert_eq!(data, vec!["{\"delta\": \"Hel\"}", "{\"delta\": \"lo\"}"]);
+    }
+    
+    #[tokio::test]
+    async fn only_data_lines_carry_a_payload() {
+        let lines = read_lines(&[": keep-alive\n\nevent: message\ndata: {}\n\ndata: [DONE]\n\n"]).await;
+        assert_eq!(lines, vec![": keep-alive", "", "event: message", "data: {}", "", "data: [DONE]", ""]);
+        
+        let data: Vec<&str> = lines.iter().filter_map(|line| sse_data(line)).collect();
+        assert_eq!(data, vec!["{}", "[DONE]"]);
+    }
+    
+    #[tokio::test]
+    async fn ndjson_last_line_without_newline_is_returned() {
+        let lines = read_lines(&["{\"response\": \"a\"}\n{\"resp", "onse\": \"b\", \"done\": true}"]).await;
+        assert_eq!(lines, vec!["{\"response\": \"a\"}", "{\"response\": \"b\", \"done\": true}"]);
+    }
+    
+    #[tokio::test]
+    async fn empty_body_has_no_lines() {
+        assert!(read_lines(&[]).await.is_empty());
+    }
+    
+    #[test]
+    fn stream_without_tokens_has_no_timing() {
+        assert!(StreamTimer::start().finish().is_none());
+    }
+    
+    #[test]
+    fn single_token_has_first_token_time_but_no_inter_token_latency() {
+        let mut timer = StreamTimer::start();
+        timer.record_token();
+        let timing = timer.finish().unwrap();
+        assert!(timing.inter_token_latencies_ms.is_empty());
+        assert_eq!(timing.avg_inter_token_latency_ms(), None);
+    }
+    
+    #[test]
+    fn inter_token_latency_averages_the_gaps() {
+        let timing = StreamTiming { time_to_first_token_ms: 100, inter_token_latencies_ms: vec![10.0, 30.0] };
+        assert_eq!(timing.avg_inter_token_latency_ms(), Some(20.0));
+    }
+    
+    #[test]
+    fn tokens_per_second_counts_only_the_generation_phase() {
+        let timing = StreamTiming { time_to_first_token_ms: 500, inter_token_latencies_ms: Vec::new() };
+        assert_eq!(timing.tokens_per_second(100, 2500), Some(50.0));
+    }
+    
+    #[test]
+    fn tokens_per_second_needs_output_and_generation_time() {
+        let timing = StreamTiming { time_to_first_token_ms: 500, inter_token_latencies_ms: Vec::new() };
+        assert_eq!(timing.tokens_per_second(0, 2500), None);
+        // A single token that ends the response leaves no generation phase to measure
+        assert_eq!(timing.tokens_per_second(1, 500), None);
+        assert_eq!(timing.tokens_per_second(1, 400), None);
+    }
+}
@@ -0,0 +1,396 @@
+// Local tokenizers for counting tokens when a provider doesn't report usage
+use crate::utils::config::{EvalEdsConfig, TokenizerConfig, TokenizerKind};
+use crate::utils::error::{Result, EvalError};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use fancy_regex::Regex;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Counts tokens the way a model's own tokenizer would
+pub trait Tokenizer: Send + Sync {
+    fn count_tokens(&self, text: &str) -> u32;
+}
+
+/// Per-provider tokenizers loaded from `EvalEdsConfig.providers[*].tokenizers`
+#[derive(Clone, Default)]
+pub struct TokenizerRegistry {
+    providers: HashMap<String, ProviderTokenizers>,
+}
+
+#[derive(Clone, Default)]
+struct ProviderTokenizers {
+    models: HashMap<String, Arc<dyn Tokenizer>>,
+    default: Option<Arc<dyn Tokenizer>>,
+}
+
+impl TokenizerRegistry {
+    /// Load every configured vocab file up front. A vocab that fails to load is skipped with a
+    /// warning, so its models fall back to estimated counts instead of failing the provider.
+    /// Models sharing a vocab file share one loaded tokenizer.
+    pub fn from_config(config: &EvalEdsConfig) -> Self {
+        let mut loaded: HashMap<(TokenizerKind, String), Option<Arc<dyn Tokenizer>>> = HashMap::new();
+        let mut load = |provider: &str, tokenizer: &TokenizerConfig| -> Option<Arc<dyn Tokenizer>> {
+            let key = (tokenizer.kind, tokenizer.path.clone());
+            if let Some(existing) = loaded.get(&key) {
+                return existing.clone();
+            }
+            let created = match load_tokenizer(tokenizer) {
+                Ok(created) => Some(created),
+                Err(e) => {
+                    log::warn!("{}: skipping tokenizer, token counts will be estimated: {}", provider, e);
+                    None
+                }
+            };
+            loaded.insert(key, created.clone());
+            created
+        };
+        
+        let mut providers = HashMap::new();
+        for (name, info) in &config.providers {
+            if !info.enabled {
+                continue;
+            }
+            
+            let mut models = HashMap::new();
+            for (model, tokenizer) in &info.tokenizers {
+                if let Some(tokenizer) = load(name, tokenizer) {
+                    models.insert(model.clone(), tokenizer);
+                }
+            }
+            let default = info.default_tokenizer.as_ref().and_then(|tokenizer| load(name, tokenizer));
+            
+            if !models.is_empty() || default.is_some() {
+                providers.insert(name.clone(), ProviderTokenizers { models, default });
+            }
+        }
+        
+        Self { providers }
+    }
+    
+    /// Exact model match first, then the provider's default tokenizer
+    pub fn lookup(&self, provider: &str, model: &str) -> Option<&Arc<dyn Tokenizer>> {
+        let tokenizers = self.providers.get(provider)?;
+        tokenizers.models.get(model).or(tokenizers.default.as_ref())
+    }
+}
+
+pub fn load_tokenizer(config: &TokenizerConfig) -> Result<Arc<dyn Tokenizer>> {
+    let path = expand_path(&config.path);
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| EvalError::ConfigError(format!("cannot read tokenizer vocab {}: {}", path.display(), e)))?;
+    
+    let tokenizer: Arc<dyn Tokenizer> = match config.kind {
+        TokenizerKind::Bpe => Arc::new(BpeTokenizer::from_tiktoken(&contents)?),
+        TokenizerKind::Sentencepiece => Arc::new(SentencePieceTokenizer::from_vocab(&contents)?),
+    };
+    Ok(tokenizer)
+}
+
+fn expand_path(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Byte-level BPE over a tiktoken rank file (`<base64 token> <rank>` per line),
+/// as published for the OpenAI encodings (cl100k_base, o200k_base).
+pub struct BpeTokenizer {
+    ranks: HashMap<Vec<u8>, u32>,
+    pretokenizer: Regex,
+}
+
+// cl100k_base split pattern. The `\s+(?!\S)` lookahead leaves the last space of a run for the
+// following word, which is why this needs fancy-regex rather than the regex crate.
+const BPE_PATTERN: &str = r"(?i:'s|'t|'re|'ve|'m|'ll|'d)|[^\r\n\p{L}\p{N}]?\p{L}+|\p{N}{1,3}| ?[^\s\p{L}\p{N}]+[\r\n]*|\s*[\r\n]+|\s+(?!\S)|\s+";
+
+impl BpeTokenizer {
+    pub fn from_tiktoken(contents: &str) -> Result<Self> {
+        let mut ranks = HashMap::new();
+        
+        for (line_number, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            
+            let parsed = line.split_once(' ').and_then(|(token, rank)| {
+                Some((BASE64.decode(token).ok()?, rank.trim().parse::<u32>().ok()?))
+            });
+            match parsed {
+                Some((token, rank)) => {
+                    ranks.insert(token, rank);
+                }
+                None => {
+                    return Err(EvalError::ConfigError(format!(
+                        "invalid BPE vocab line {}: expected '<base64 token> <rank>'", line_number + 1
+                    )));
+                }
+            }
+        }
+        
+        if ranks.is_empty() {
+            return Err(EvalError::ConfigError("BPE vocab file is empty".to_string()));
+        }
+        
+        Ok(Self {
+            ranks,
+            pretokenizer: Regex::new(BPE_PATTERN).expect("valid BPE pattern"),
+        })
+    }
+    
+    /// Number of tokens after repeatedly merging the lowest-ranked adjacent pair
+    fn count_piece(&self, piece: &[u8]) -> u32 {
+        if self.ranks.contains_key(piece) {
+            return 1;
+        }
+        
+        // Part boundaries; part i spans bounds[i]..bounds[i + 1]
+        let mut bounds: Vec<usize> = (0..=piece.len()).collect();
+        
+        while bounds.len() > 2 {
+            let best = (0..bounds.len() - 2)
+                .filter_map(|i| self.ranks.get(&piece[bounds[i]..bounds[i + 2]]).map(|rank| (*rank, i)))
+                .min();
+            
+            match best {
+                Some((_, i)) => {
+                    bounds.remove(i + 1);
+                }
+                None => break,
+            }
+        }
+        
+        (bounds.len() - 1) as u32
+    }
+}
+
+impl Tokenizer for BpeTokenizer {
+    fn count_tokens(&self, text: &str) -> u32 {
+        self.pretokenizer
+            .find_iter(text)
+            // The pattern's only lookahead is a single character, so matching can't hit the backtrack limit
+            .flatten()
+            .map(|piece| self.count_piece(piece.as_str().as_bytes()))
+            .sum()
+    }
+}
+
+/// SentencePiece segmentation over an exported `.vocab` file (`<piece>\t<score>` per line).
+/// Picks the highest-scoring segmentation, which matches unigram models exactly and
+/// tracks BPE-type models (Llama, Mistral, Gemma) closely.
+pub struct SentencePieceTokenizer {
+    pieces: HashMap<String, f32>,
+    max_piece_chars: usize,
+    byte_fallback: bool,
+    unknown_score: f32,
+}
+
+impl SentencePieceTokenizer {
+    pub fn from_vocab(contents: &str) -> Result<Self> {
+        let mut pieces = HashMap::new();
+        
+        for (line_number, line) in contents.lines().enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+            
+            let parsed = line.split_once('\t')
+                .and_then(|(piece, score)| Some((piece, score.trim().parse::<f32>().ok()?)));
+            match parsed {
+                Some((piece, score)) => {
+                    pieces.insert(piece.to_string(), score);
+                }
+                None => {
+                    return Err(EvalError::ConfigError(format!(
+                        "invalid SentencePiece vocab line {}: expected '<piece>\\t<score>'", line_number + 1
+                    )));
+                }
+            }
+        }
+        
+        if pieces.is_empty() {
+            return Err(EvalError::ConfigError("SentencePiece vocab file is empty".to_string()));
+        }
+        
+        let max_piece_chars = pieces.keys().map(|p| p.chars().count()).max().unwrap_or(1);
+        let byte_fallback = pieces.contains_key("<0x00>");
+        let min_score = pieces.values().cloned().fold(f32::INFINITY, f32::min);
+        
+        Ok(Self {
+            pieces,
+            max_piece_chars,
+            byte_fallback,
+            unknown_score: min_score - 10.0,
+        })
+    }
+}
+
+impl Tokenizer for SentencePieceTokenizer {
+    fn count_tokens(&self, text: &str) -> u32 {
+        if text.is_empty() {
+            return 0;
+        }
+        
+        // SentencePiece marks word boundaries with U+2581 and adds a leading one
+        let normalized = format!("\u{2581}{}", text.replace(' ', "\u{2581}"));
+        let offsets: Vec<usize> = normalized.char_indices().map(|(i, _)| i)
+            .chain(std::iter::once(normalized.len()))
+            .collect();
+        let char_count = offsets.len() - 1;
+        
+        // best[i] = (score, tokens) of the best segmentation of the first i characters
+        let mut best: Vec<Option<(f32, u32)>> = vec![None; char_count + 1];
+        best[0] = Some((0.0, 0));
+        
+        for end in 1..=char_count {
+            let start_min = end.saturating_sub(self.max_piece_chars);
+            
+            for start in start_min..end {
+                let (score, tokens) = match best[start] {
+                    Some(prefix) => prefix,
+                    None => continue,
+                };
+                
+                let piece = &normalized[offsets[start]..offsets[end]];
+                let candidate = match self.pieces.get(piece) {
+                    Some(piece_score) => (score + piece_score, tokens + 1),
+                    // Unknown single characters become bytes or one unknown token
+                    None if start + 1 == end => {
+                        let pieces = if self.byte_fallback { piece.len() as u32 } else { 1 };
+                        (score + self.unknown_score * pieces as f32, tokens + pieces)
+                    },
+                    None => continue,
+                };
+                
+                let better = match best[end] {
+                    Some((current, _)) => candidate.0 > current,
+                    None => true,
+                };
+                if better {
+                    best[end] = Some(candidate);
+                }
+            }
+        }
+        
+        best[char_count].map(|(_, tokens)| tokens).unwrap_or(char_count as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn pieces(text: &str) -> Vec<String> {
+        Regex::new(BPE_PATTERN).unwrap()
+            .find_iter(text)
+            .map(|piece| piece.unwrap().as_str().to_string())
+            .collect()
+    }
+    
+    /// Every single byte plus the given merged tokens, ranked in order
+    fn bpe(merges: &[&str]) -> BpeTokenizer {
+        let mut contents = String::new();
+        for byte in 0..=255u8 {
+            contents.push_str(&format!("{} {}\n", BASE64.encode([byte]), byte));
+        }
+        for (i, token) in merges.iter().enumerate() {
+            contents.push_str(&format!("{} {}\n", BASE64.encode(token.as_bytes()), 256 + i));
+        }
+        BpeTokenizer::from_tiktoken(&contents).unwrap()
+    }
+    
+    // Splits as produced by tiktoken's cl100k_base pretokenizer
+    #[test]
+    fn pretokenizer_matches_cl100k_splits() {
+        assert_eq!(pieces("Hello, world!"), ["Hello", ",", " world", "!"]);
+        assert_eq!(pieces("I'm here"), ["I", "'m", " here"]);
+        assert_eq!(pieces("1234567"), ["123", "456", "7"]);
+        assert_eq!(pieces("line one\n\nline two"), ["line", " one", "\n\n", "line", " two"]);
+    }
+    
+    #[test]
+    fn whitespace_runs_leave_the_last_space_for_the_next_word() {
+        assert_eq!(pieces("hello   world"), ["hello", "  ", " world"]);
+        assert_eq!(pieces("    indented"), ["   ", " indented"]);
+        assert_eq!(pieces("trailing   "), ["trailing", "   "]);
+        assert_eq!(pieces("end  \n"), ["end", "  \n"]);
+    }
+    
+    #[test]
+    fn bpe_counts_merged_pieces() {
+        let tokenizer = bpe(&["he", "ll", "hell", "hello", " w", " wo", " wor", "ld"]);
+        assert_eq!(tokenizer.count_tokens("hello"), 1);
+        assert_eq!(tokenizer.count_tokens("hello world"), 3);
+        assert_eq!(tokenizer.count_tokens("help"), 3);
+        assert_eq!(tokenizer.count_tokens(""), 0);
+    }
+    
+    #[test]
+    fn bpe_merges_lowest_rank_first() {
+        // "bc" wins over "ab", after which neither "abc" nor "bcd" exists
+        assert_eq!(bpe(&["bc", "ab", "cd"]).count_tokens("abcd"), 3);
+        assert_eq!(bpe(&["ab", "bc", "cd"]).count_tokens("abcd"), 2);
+    }
+    
+    #[test]
+    fn bpe_falls_back_to_single_bytes() {
+        let tokenizer = bpe(&[]);
+        assert_eq!(tokenizer.count_tokens("abc"), 3);
+        // Multi-byte characters cost one token per UTF-8 byte without merges
+        assert_eq!(tokenizer.count_tokens("\u{e9}"), 2);
+    }
+    
+    #[test]
+    fn invalid_vocab_lines_are_rejected() {
+        assert!(BpeTokenizer::from_tiktoken("").is_err());
+        assert!(BpeTokenizer::from_tiktoken("not-base64! 1").is_err());
+        assert!(BpeTokenizer::from_tiktoken("aGVsbG8= rank").is_err());
+        assert!(BpeTokenizer::from_tiktoken("aGVsbG8= 1\n").is_ok());
+    }
+    
+    #[test]
+    fn unreadable_vocab_files_are_skipped() {
+        let mut config = EvalEdsConfig::default();
+        let provider = config.providers.get_mut("openai").unwrap();
+        provider.enabled = true;
+        provider.tokenizers.insert("gpt-4".to_string(), TokenizerConfig {
+            kind: TokenizerKind::Bpe,
+            path: "/nonexistent/cl100k_base.tiktoken".to_string(),
+        });
+        provider.default_tokenizer = Some(TokenizerConfig {
+            kind: TokenizerKind::Sentencepiece,
+            path: "/nonexistent/model.vocab".to_string(),
+        });
+        
+        let registry = TokenizerRegistry::from_config(&config);
+        assert!(registry.lookup("openai", "gpt-4").is_none());
+        assert!(registry.lookup("openai", "gpt-3.5-turbo").is_none());
+    }
+    
+    /// Reference counts from tiktoken; run with EVALEDS_CL100K_VOCAB pointing at cl100k_base.tiktoken
+    #[test]
+    fn cl100k_reference_counts() {
+        let path = match std::env::var("EVALEDS_CL100K_VOCAB") {
+            Ok(path) => path,
+            Err(_) => return,
+        };
+        let tokenizer = BpeTokenizer::from_tiktoken(&std::fs::read_to_string(path).unwrap()).unwrap();
+        
+        assert_eq!(tokenizer.count_tokens("hello world"), 2);
+        assert_eq!(tokenizer.count_tokens("Hello, world!"), 4);
+        assert_eq!(tokenizer.count_tokens("The quick brown fox jumps over the lazy dog."), 10);
+        assert_eq!(tokenizer.count_tokens("1234567"), 3);
+    }
+    
+    #[test]
+    fn sentencepiece_prefers_the_highest_scoring_segmentation() {
+        let vocab = "\u{2581}hello\t-1.0\n\u{2581}he\t-2.0\nllo\t-2.0\n\u{2581}world\t-1.5\n\u{2581}\t-3.0\n";
+        let tokenizer = SentencePieceTokenizer::from_vocab(vocab).unwrap();
+        assert_eq!(tokenizer.count_tokens("hello world"), 2);
+        assert_eq!(tokenizer.count_tokens(""), 0);
+        // Unknown characters without byte fallback count as one token each
+        assert_eq!(tokenizer.count_tokens("hello xy"), 4);
+    }
+}
\ No newline at end of file
@@ -1,5 +1,5 @@
 // Web server implementation with templates and handlers
-use crate::core::evaluation::Evaluation;
+use crate::core::evaluation::{Evaluation, EvaluationRun, ExecutionResult, ResolvedPrompt};
 use crate::utils::error::Result;
 use axum::{
     extract::{Path, Query, State},
@@ -17,6 +17,7 @@ use tower_http::services::ServeDir;
 #[derive(Clone)]
 pub struct AppState {
     pub evaluation: Arc<Evaluation>,
+    pub runs: Arc<Vec<EvaluationRun>>,        // Every stored run, oldest first; pick one with ?run=N
     pub handlebars: Arc<Handlebars<'static>>,
 }
 
@@ -26,7 +27,7 @@ pub struct WebServer {
 }
 
 impl WebServer {
-    pub async fn new(evaluation: &Evaluation, port: u16) -> Result<Self> {
+    pub async fn new(evaluation: &Evaluation, runs: Vec<EvaluationRun>, port: u16) -> Result<Self> {
         // Set up Handlebars
         let mut handlebars = Handlebars::new();
         
@@ -38,6 +39,7 @@ impl WebServer {
         
         let state = AppState {
             evaluation: Arc::new(evaluation.clone()),
+            runs: Arc::new(runs),
             handlebars: Arc::new(handlebars),
         };
         
@@ -68,69 +70,165 @@ impl WebServer {
     }
 }
 
-async fn dashboard_handler(State(state): State<AppState>) -> impl IntoResponse {
-    let evaluation = &state.evaluation;
+/// The evaluation as of the run picked with `?run=N`, or as of its latest run
+fn selected_run(state: &AppState, params: &HashMap<String, String>) -> std::result::Result<(Evaluation, Option<u32>), Response> {
+    let requested = match params.get("run") {
+        Some(requested) => requested,
+        None => return Ok(((*state.evaluation).clone(), state.runs.last().map(|run| run.number))),
+    };
+    
+    let run = requested.parse::<u32>().ok()
+        .and_then(|number| state.runs.iter().find(|run| run.number == number));
+    match run {
+        Some(run) => Ok(((*state.evaluation).clone().at_run(run), Some(run.number))),
+        None => Err((StatusCode::NOT_FOUND, format!("Run {} not found", requested)).into_response()),
+    }
+}
+
+/// Entries for the run picker in the page header
+fn run_links(state: &AppState) -> serde_json::Value {
+    state.runs.iter()
+        .map(|run| json!({
+            "number": run.number,
+            "status": run.status,
+            "started_at": run.started_at.format("%Y-%m-%d %H:%M").to_string(),
+        }))
+        .collect()
+}
+
+async fn dashboard_handler(
+    State(state): State<AppState>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Response {
+    let (evaluation, run) = match selected_run(&state, &params) {
+        Ok(selected) => selected,
+        Err(response) => return response,
+    };
     
     let data = json!({
         "evaluation": evaluation,
+        "runs": run_links(&state),
+        "run": run,
         "title": format!("EvalEds - {}", evaluation.name),
         "page": "dashboard"
     });
     
     match state.handlebars.render("dashboard", &data) {
-        Ok(html) => Html(html),
+        Ok(html) => Html(html).into_response(),
         Err(e) => {
             eprintln!("Template error: {}", e);
-            Html(format!("<h1>Error rendering template: {}</h1>", e))
+            Html(format!("<h1>Error rendering template: {}</h1>", e)).into_response()
         }
     }
 }
 
-async fn comparison_handler(State(state): State<AppState>) -> impl IntoResponse {
-    let evaluation = &state.evaluation;
+async fn comparison_handler(
+    State(state): State<AppState>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Response {
+    let (evaluation, run) = match selected_run(&state, &params) {
+        Ok(selected) => selected,
+        Err(response) => return response,
+    };
     
     let data = json!({
         "evaluation": evaluation,
+        "prompt_groups": comparison_groups(&evaluation),
+        "runs": run_links(&state),
+        "run": run,
         "title": format!("Comparison - {}", evaluation.name),
         "page": "comparison"
     });
     
     match state.handlebars.render("comparison", &data) {
-        Ok(html) => Html(html),
-        Err(e) => Html(format!("<h1>Error: {}</h1>", e))
+        Ok(html) => Html(html).into_response(),
+        Err(e) => Html(format!("<h1>Error: {}</h1>", e)).into_response()
+    }
+}
+
+/// Executions grouped by the prompt variation that produced them, in config order, so every
+/// model's answer to the same prompt sits side by side. Results stored before prompts were
+/// recorded on them are grouped by their rendered prompt instead.
+fn comparison_groups(evaluation: &Evaluation) -> serde_json::Value {
+    let executions = match &evaluation.results {
+        Some(results) => &results.executions,
+        None => return json!([]),
+    };
+    
+    let mut groups: Vec<(String, Option<&ResolvedPrompt>, Vec<&ExecutionResult>)> = Vec::new();
+    for execution in executions {
+        let key = match &execution.resolved_prompt {
+            Some(prompt) => format!("{}/{:?}/{:?}", prompt.prompt_index, prompt.variation_name, prompt.variation),
+            None => execution.prompt_id.clone(),
+        };
+        match groups.iter_mut().find(|(group_key, _, _)| *group_key == key) {
+            Some((_, _, members)) => members.push(execution),
+            None => groups.push((key, execution.resolved_prompt.as_ref(), vec![execution])),
+        }
     }
+    groups.sort_by_key(|(_, prompt, _)| prompt.map(|p| (p.prompt_index, p.variation)));
+    
+    groups.into_iter()
+        .enumerate()
+        .map(|(i, (_, prompt, mut members))| {
+            members.sort_by(|a, b| (&a.provider, &a.model, a.repetition).cmp(&(&b.provider, &b.model, b.repetition)));
+            json!({
+                "label": prompt.map(|p| p.label()).unwrap_or_else(|| format!("prompt {}", i + 1)),
+                "variables": prompt.map(|p| &p.variables),
+                "executions": members,
+            })
+        })
+        .collect()
 }
 
-async fn analysis_handler(State(state): State<AppState>) -> impl IntoResponse {
-    let evaluation = &state.evaluation;
+async fn analysis_handler(
+    State(state): State<AppState>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Response {
+    let (evaluation, run) = match selected_run(&state, &params) {
+        Ok(selected) => selected,
+        Err(response) => return response,
+    };
     
     let data = json!({
         "evaluation": evaluation,
+        "runs": run_links(&state),
+        "run": run,
         "title": format!("Analysis - {}", evaluation.name),
         "page": "analysis"
     });
     
     match state.handlebars.render("analysis", &data) {
-        Ok(html) => Html(html),
-        Err(e) => Html(format!("<h1>Error: {}</h1>", e))
+        Ok(html) => Html(html).into_response(),
+        Err(e) => Html(format!("<h1>Error: {}</h1>", e)).into_response()
     }
 }
 
 async fn export_handler(
     State(state): State<AppState>,
     Query(params): Query<HashMap<String, String>>,
-) -> impl IntoResponse {
+) -> Response {
+    let (evaluation, _) = match selected_run(&state, &params) {
+        Ok(selected) => selected,
+        Err(response) => return response,
+    };
     let format = params.get("format").map(|s| s.as_str()).unwrap_or("html");
     
     match format {
-        "markdown" => export_markdown(&state.evaluation).await,
-        "json" => export_json(&state.evaluation).await,
-        _ => export_html(&state.evaluation).await,
+        "markdown" => export_markdown(&evaluation).await,
+        "json" => export_json(&evaluation).await,
+        _ => export_html(&evaluation).await,
     }
 }
 
-async fn api_results(State(state): State<AppState>) -> impl IntoResponse {
-    axum::Json(&state.evaluation.results)
+async fn api_results(
+    State(state): State<AppState>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Response {
+    match selected_run(&state, &params) {
+        Ok((evaluation, _)) => axum::Json(evaluation.results).into_response(),
+        Err(response) => response,
+    }
 }
 
 async fn export_markdown(evaluation: &Evaluation) -> Response {
@@ -177,8 +275,23 @@ async fn generate_markdown_report(evaluation: &Evaluation) -> String {
         report.push_str(&format!("- **Total Executions**: {}\n", results.summary.total_executions));
         report.push_str(&format!("- **Successful**: {}\n", results.summary.successful_executions));
         report.push_str(&format!("- **Failed**: {}\n", results.summary.failed_executions));
+        report.push_str(&format!("- **Timed Out**: {}\n", results.summary.timed_out_executions));
+        if results.summary.skipped_executions > 0 {
+            report.push_str(&format!("- **Not Finished (budget reached or cancelled)**: {}\n", results.summary.skipped_executions));
+        }
         report.push_str(&format!("- **Success Rate**: {:.1}%\n", results.summary.success_rate));
         report.push_str(&format!("- **Total Cost**: ${:.4}\n", results.summary.total_cost));
+        let cache_savings = results.analysis.cost_breakdown.cache_savings;
+        if cache_savings > 0.0 {
+            report.push_str(&format!("- **Saved by Response Cache**: ${:.4}\n", cache_savings));
+        }
+        if let Some(batch_cost) = results.analysis.cost_breakdown.batch_cost_estimate {
+            report.push_str(&format!("- **Estimated Batch API Cost**: ${:.4}\n", batch_cost));
+        }
+        if !results.analysis.cost_breakdown.unpriced_models.is_empty() {
+            report.push_str(&format!("- **Unpriced Models** (cost unknown): {}\n",
+                                     results.analysis.cost_breakdown.unpriced_models.join(", ")));
+        }
         report.push_str(&format!("- **Average Response Time**: {:.0}ms\n\n", results.summary.avg_response_time));
         
         if let Some(best) = &results.summary.best_performing_model {
@@ -191,21 +304,113 @@ async fn generate_markdown_report(evaluation: &Evaluation) -> String {
             report.push_str(&format!("- **Fastest Model**: {}\n", fastest));
         }
         
+        if !results.analysis.tool_call_scores.is_empty() {
+            report.push_str("\n## Tool Calling\n\n");
+            report.push_str("| Model | Executions | Tool Selection | Arguments | Exact Match | Unexpected Calls |\n");
+            report.push_str("|-------|------------|----------------|-----------|-------------|------------------|\n");
+            for score in &results.analysis.tool_call_scores {
+                report.push_str(&format!("| {}/{} | {} | {:.0}% | {:.0}% | {:.0}% | {} |\n",
+                    score.provider, score.model, score.executions,
+                    score.tool_selection_accuracy * 100.0,
+                    score.argument_accuracy * 100.0,
+                    score.exact_match_rate * 100.0,
+                    score.unexpected_calls));
+            }
+        }
+        
+        if !results.analysis.cell_variance.is_empty() {
+            report.push_str("\n## Consistency Across Repetitions\n\n");
+            report.push_str("| Model | Cell | Samples | Latency (ms) | Cost ($) | Quality | Self-Agreement |\n");
+            report.push_str("|-------|------|---------|--------------|----------|---------|----------------|\n");
+            let format_spread = |spread: &Option<crate::core::evaluation::MetricSpread>, precision: usize| match spread {
+                Some(spread) => format!("{:.*} ± {:.*}", precision, spread.mean, precision, spread.std_dev),
+                None => "-".to_string(),
+            };
+            for cell in &results.analysis.cell_variance {
+                let prompt_label = cell.resolved_prompt.as_ref().map(|p| format!("{} ", p.label())).unwrap_or_default();
+                report.push_str(&format!("| {}/{} | {}`{}` | {}/{} | {} | {} | {} | {} |\n",
+                    cell.provider, cell.model, prompt_label, cell.cell_id,
+                    cell.successful_samples, cell.samples,
+                    format_spread(&cell.latency_ms, 0),
+                    format_spread(&cell.cost_usd, 6),
+                    format_spread(&cell.quality, 2),
+                    cell.self_agreement.map(|a| format!("{:.2}", a)).unwrap_or_else(|| "-".to_string())));
+            }
+        }
+        
+        if !results.analysis.schema_conformance_by_model.is_empty() {
+            report.push_str("\n## Schema Conformance\n\n");
+            report.push_str("| Model | Executions | Parses | Valid | Failed Paths |\n");
+            report.push_str("|-------|------------|--------|-------|--------------|\n");
+            for rate in &results.analysis.schema_conformance_by_model {
+                report.push_str(&format!("| {}/{} | {} | {:.0}% | {:.0}% | {} |\n",
+                    rate.provider, rate.model, rate.executions,
+                    rate.parse_rate, rate.valid_rate,
+                    rate.failed_paths.iter().map(|p| format!("`{}`", p)).collect::<Vec<_>>().join(", ")));
+            }
+        }
+        
         report.push_str("\n## Detailed Results\n\n");
         
         for execution in &results.executions {
             report.push_str(&format!("### {} - {}\n\n", execution.provider, execution.model));
             report.push_str(&format!("**Status**: {:?}\n", execution.status));
+            if let Some(prompt) = &execution.resolved_prompt {
+                report.push_str(&format!("**Prompt**: {}\n", prompt.label()));
+            }
+            if !results.analysis.cell_variance.is_empty() {
+                report.push_str(&format!("**Cell**: `{}` (sample {})\n", execution.cell_id, execution.repetition + 1));
+            }
             report.push_str(&format!("**Response Time**: {}ms\n", execution.metadata.response_time_ms));
-            report.push_str(&format!("**Cost**: ${:.6}\n", execution.metadata.cost_usd));
-            report.push_str(&format!("**Tokens**: {} in, {} out\n\n", 
+            if execution.metadata.cache_hit {
+                report.push_str("**Cache**: hit (cost not paid again; latency from the original call)\n");
+            }
+            if execution.metadata.cost_unknown {
+                report.push_str("**Cost**: unknown (no pricing configured)\n");
+            } else {
+                report.push_str(&format!("**Cost**: ${:.6}\n", execution.metadata.cost_usd));
+            }
+            report.push_str(&format!("**Tokens**: {} in, {} out\n", 
                 execution.metadata.token_count_input, 
                 execution.metadata.token_count_output));
+            if let Some(ttft) = execution.metadata.time_to_first_token_ms {
+                report.push_str(&format!("**Time to First Token**: {}ms\n", ttft));
+            }
+            if let Some(tps) = execution.metadata.tokens_per_second {
+                report.push_str(&format!("**Throughput**: {:.1} tokens/s\n", tps));
+            }
+            if !execution.images.is_empty() {
+                let paths: Vec<String> = execution.images.iter().map(|i| format!("`{}`", i.path)).collect();
+                report.push_str(&format!("**Images**: {}\n", paths.join(", ")));
+            }
+            report.push_str("\n");
             
             if matches!(execution.status, crate::core::evaluation::ExecutionStatus::Success) {
                 report.push_str("**Output**:\n```\n");
                 report.push_str(&execution.output);
                 report.push_str("\n```\n\n");
+                
+                let conformance = results.analysis.schema_conformance.iter()
+                    .find(|c| c.execution_id == execution.id);
+                if let Some(conformance) = conformance {
+                    if conformance.valid {
+                        report.push_str("**Schema**: valid\n\n");
+                    } else {
+                        report.push_str("**Schema Violations**:\n");
+                        for violation in &conformance.violations {
+                            report.push_str(&format!("- `{}`: {}\n", violation.path, violation.message));
+                        }
+                        report.push_str("\n");
+                    }
+                }
+                
+                if !execution.tool_calls.is_empty() {
+                    report.push_str("**Tool Calls**:\n");
+                    for call in &execution.tool_calls {
+                        report.push_str(&format!("- `{}({})`\n", call.name, call.arguments));
+                    }
+                    report.push_str("\n");
+                }
             } else if let Some(error) = &execution.metadata.error {
                 report.push_str(&format!("**Error**: {}\n\n", error));
             }
@@ -275,6 +480,9 @@ const LAYOUT_TEMPLATE: &str = r#"<!DOCTYPE html>
         }
         .nav a:hover { background: #f1f5f9; color: #0f172a; }
         .nav a.active { background: #3b82f6; color: white; }
+        .runs { margin-top: 12px; color: #64748b; font-size: 14px; }
+        .runs a { color: #64748b; text-decoration: none; padding: 2px 8px; border-radius: 4px; }
+        .runs a.active { background: #e2e8f0; color: #0f172a; }
         .card { 
             background: white; padding: 24px; border-radius: 8px; 
             box-shadow: 0 1px 3px rgba(0,0,0,0.1); margin-bottom: 20px;
@@ -312,10 +520,18 @@ const LAYOUT_TEMPLATE: &str = r#"<!DOCTYPE html>
             <p>{{evaluation.description}}</p>
             {{/if}}
             <nav class="nav">
-                <a href="/" {{#eq page "dashboard"}}class="active"{{/eq}}>📊 Dashboard</a>
-                <a href="/comparison" {{#eq page "comparison"}}class="active"{{/eq}}>🔄 Comparison</a>
-                <a href="/analysis" {{#eq page "analysis"}}class="active"{{/eq}}>🔍 Analysis</a>
+                <a href="/{{#if run}}?run={{run}}{{/if}}" {{#eq page "dashboard"}}class="active"{{/eq}}>📊 Dashboard</a>
+                <a href="/comparison{{#if run}}?run={{run}}{{/if}}" {{#eq page "comparison"}}class="active"{{/eq}}>🔄 Comparison</a>
+                <a href="/analysis{{#if run}}?run={{run}}{{/if}}" {{#eq page "analysis"}}class="active"{{/eq}}>🔍 Analysis</a>
             </nav>
+            {{#if runs}}
+            <div class="runs">
+                Run:
+                {{#each runs}}
+                <a href="?run={{number}}" title="{{status}}, {{started_at}}" {{#eq number ../run}}class="active"{{/eq}}>#{{number}}</a>
+                {{/each}}
+            </div>
+            {{/if}}
         </div>
         {{{body}}}
     </div>
@@ -339,20 +555,32 @@ const DASHBOARD_TEMPLATE: &str = r#"{{#*inline "page"}}
             <div class="metric-value">${{evaluation.results.summary.total_cost}}</div>
             <div class="metric-label">Total Cost</div>
         </div>
+        {{#if evaluation.results.analysis.cost_breakdown.cache_savings}}
+        <div class="metric">
+            <div class="metric-value">${{evaluation.results.analysis.cost_breakdown.cache_savings}}</div>
+            <div class="metric-label">Saved by Cache</div>
+        </div>
+        {{/if}}
         <div class="metric">
             <div class="metric-value">{{evaluation.results.summary.avg_response_time}}ms</div>
             <div class="metric-label">Avg Response Time</div>
         </div>
+        {{#if evaluation.results.summary.timed_out_executions}}
+        <div class="metric">
+            <div class="metric-value">{{evaluation.results.summary.timed_out_executions}}</div>
+            <div class="metric-label">Timed Out</div>
+        </div>
+        {{/if}}
     </div>
     {{/if}}
 </div>
 
 <div class="card">
     <h2>Quick Actions</h2>
-    <a href="/comparison" class="btn">🔄 Compare Results</a>
-    <a href="/analysis" class="btn">🔍 View Analysis</a>
-    <a href="/export?format=markdown" class="btn btn-secondary">📄 Export Markdown</a>
-    <a href="/export?format=json" class="btn btn-secondary">💾 Export JSON</a>
+    <a href="/comparison{{#if run}}?run={{run}}{{/if}}" class="btn">🔄 Compare Results</a>
+    <a href="/analysis{{#if run}}?run={{run}}{{/if}}" class="btn">🔍 View Analysis</a>
+    <a href="/export?format=markdown{{#if run}}&run={{run}}{{/if}}" class="btn btn-secondary">📄 Export Markdown</a>
+    <a href="/export?format=json{{#if run}}&run={{run}}{{/if}}" class="btn btn-secondary">💾 Export JSON</a>
 </div>
 
 {{#if evaluation.results}}
@@ -366,8 +594,11 @@ const DASHBOARD_TEMPLATE: &str = r#"{{#*inline "page"}}
         </div>
         <div class="execution-content">
             <p><strong>Response Time:</strong> {{metadata.response_time_ms}}ms</p>
-            <p><strong>Cost:</strong> ${{metadata.cost_usd}}</p>
+            <p><strong>Cost:</strong> ${{metadata.cost_usd}}{{#if metadata.cache_hit}} (cached){{/if}}</p>
             <p><strong>Tokens:</strong> {{metadata.token_count_input}} in, {{metadata.token_count_output}} out</p>
+            {{#if images}}
+            <p><strong>Images:</strong> {{#each images}}<code>{{path}}</code> {{/each}}</p>
+            {{/if}}
         </div>
     </div>
     {{/each}}
@@ -381,8 +612,13 @@ const COMPARISON_TEMPLATE: &str = r#"{{#*inline "page"}}
 <div class="card">
     <h2>Model Comparison</h2>
     {{#if evaluation.results}}
+    {{#each prompt_groups}}
+    <h3 style="margin: 20px 0 8px;">{{label}}</h3>
+    {{#if variables}}
+    <p style="margin-bottom: 12px; font-size: 14px;">{{#each variables}}<code>{{@key}}={{this}}</code> {{/each}}</p>
+    {{/if}}
     <div class="grid">
-        {{#each evaluation.results.executions}}
+        {{#each executions}}
         <div class="execution-card">
             <div class="execution-header">
                 <h3>{{provider}}/{{model}}</h3>
@@ -391,8 +627,13 @@ const COMPARISON_TEMPLATE: &str = r#"{{#*inline "page"}}
             <div class="execution-content">
                 <div style="margin-bottom: 12px;">
                     <span><strong>⏱️</strong> {{metadata.response_time_ms}}ms</span>
-                    <span style="margin-left: 16px;"><strong>💰</strong> ${{metadata.cost_usd}}</span>
+                    <span style="margin-left: 16px;"><strong>💰</strong> ${{metadata.cost_usd}}{{#if metadata.cache_hit}} (cached){{/if}}</span>
                 </div>
+                {{#if images}}
+                <div style="margin-bottom: 12px; font-size: 14px;">
+                    <strong>🖼️</strong> {{#each images}}<code>{{path}}</code> {{/each}}
+                </div>
+                {{/if}}
                 <div style="background: #f8fafc; padding: 12px; border-radius: 4px; font-size: 14px;">
                     {{#if output}}
                     {{output}}
@@ -404,6 +645,7 @@ const COMPARISON_TEMPLATE: &str = r#"{{#*inline "page"}}
         </div>
         {{/each}}
     </div>
+    {{/each}}
     {{else}}
     <p>No results available yet. Run the evaluation first.</p>
     {{/if}}
@@ -439,6 +681,131 @@ const ANALYSIS_TEMPLATE: &str = r#"{{#*inline "page"}}
     {{/if}}
 </div>
 
+{{#if evaluation.results.analysis.performance_stats.avg_time_to_first_token}}
+<div class="card">
+    <h2>Streaming Latency</h2>
+    <div class="grid">
+        <div class="metric">
+            <div class="metric-value">{{evaluation.results.analysis.performance_stats.avg_time_to_first_token}}ms</div>
+            <div class="metric-label">Avg Time to First Token</div>
+        </div>
+        <div class="metric">
+            <div class="metric-value">{{evaluation.results.analysis.performance_stats.median_time_to_first_token}}ms</div>
+            <div class="metric-label">Median Time to First Token</div>
+        </div>
+        {{#if evaluation.results.analysis.performance_stats.avg_tokens_per_second}}
+        <div class="metric">
+            <div class="metric-value">{{evaluation.results.analysis.performance_stats.avg_tokens_per_second}}</div>
+            <div class="metric-label">Avg Tokens / Second</div>
+        </div>
+        {{/if}}
+    </div>
+    <table style="width: 100%; border-collapse: collapse; margin-top: 20px;">
+        <thead>
+            <tr style="border-bottom: 2px solid #e2e8f0;">
+                <th style="text-align: left; padding: 12px;">Provider/Model</th>
+                <th style="text-align: right; padding: 12px;">Avg TTFT</th>
+                <th style="text-align: right; padding: 12px;">Inter-Token Latency</th>
+                <th style="text-align: right; padding: 12px;">Tokens/s</th>
+                <th style="text-align: right; padding: 12px;">Streamed</th>
+            </tr>
+        </thead>
+        <tbody>
+            {{#each evaluation.results.analysis.performance_stats.streaming_by_model}}
+            <tr style="border-bottom: 1px solid #f1f5f9;">
+                <td style="padding: 12px; font-weight: 500;">{{@key}}</td>
+                <td style="padding: 12px; text-align: right;">{{avg_time_to_first_token}}ms</td>
+                <td style="padding: 12px; text-align: right;">{{#if avg_inter_token_latency}}{{avg_inter_token_latency}}ms{{else}}-{{/if}}</td>
+                <td style="padding: 12px; text-align: right;">{{#if avg_tokens_per_second}}{{avg_tokens_per_second}}{{else}}-{{/if}}</td>
+                <td style="padding: 12px; text-align: right;">{{streamed_executions}}</td>
+            </tr>
+            {{/each}}
+        </tbody>
+    </table>
+</div>
+{{/if}}
+
+{{#if evaluation.results.analysis.cell_variance}}
+<div class="card">
+    <h2>Consistency Across Repetitions</h2>
+    <table style="width: 100%; border-collapse: collapse;">
+        <thead>
+            <tr style="border-bottom: 2px solid #e2e8f0;">
+                <th style="text-align: left; padding: 12px;">Provider/Model</th>
+                <th style="text-align: left; padding: 12px;">Cell</th>
+                <th style="text-align: right; padding: 12px;">Samples</th>
+                <th style="text-align: right; padding: 12px;">Latency (mean ± sd)</th>
+                <th style="text-align: right; padding: 12px;">Cost (mean ± sd)</th>
+                <th style="text-align: right; padding: 12px;">Quality (mean ± sd)</th>
+                <th style="text-align: right; padding: 12px;">Self-Agreement</th>
+            </tr>
+        </thead>
+        <tbody>
+            {{#each evaluation.results.analysis.cell_variance}}
+            <tr style="border-bottom: 1px solid #f1f5f9;">
+                <td style="padding: 12px; font-weight: 500;">{{provider}}/{{model}}</td>
+                <td style="padding: 12px;">{{#each resolved_prompt.variables}}<code>{{@key}}={{this}}</code> {{/each}}<code>{{cell_id}}</code></td>
+                <td style="padding: 12px; text-align: right;">{{successful_samples}}/{{samples}}</td>
+                <td style="padding: 12px; text-align: right;">{{#if latency_ms}}{{latency_ms.mean}}ms ± {{latency_ms.std_dev}}{{else}}-{{/if}}</td>
+                <td style="padding: 12px; text-align: right;">{{#if cost_usd}}${{cost_usd.mean}} ± {{cost_usd.std_dev}}{{else}}-{{/if}}</td>
+                <td style="padding: 12px; text-align: right;">{{#if quality}}{{quality.mean}} ± {{quality.std_dev}}{{else}}-{{/if}}</td>
+                <td style="padding: 12px; text-align: right;">{{#if self_agreement}}{{self_agreement}}{{else}}-{{/if}}</td>
+            </tr>
+            {{/each}}
+        </tbody>
+    </table>
+</div>
+{{/if}}
+
+{{#if evaluation.results.analysis.schema_conformance_by_model}}
+<div class="card">
+    <h2>Schema Conformance</h2>
+    <table style="width: 100%; border-collapse: collapse;">
+        <thead>
+            <tr style="border-bottom: 2px solid #e2e8f0;">
+                <th style="text-align: left; padding: 12px;">Provider/Model</th>
+                <th style="text-align: right; padding: 12px;">Executions</th>
+                <th style="text-align: right; padding: 12px;">Parses</th>
+                <th style="text-align: right; padding: 12px;">Valid</th>
+                <th style="text-align: left; padding: 12px;">Failed Paths</th>
+            </tr>
+        </thead>
+        <tbody>
+            {{#each evaluation.results.analysis.schema_conformance_by_model}}
+            <tr style="border-bottom: 1px solid #f1f5f9;">
+                <td style="padding: 12px; font-weight: 500;">{{provider}}/{{model}}</td>
+                <td style="padding: 12px; text-align: right;">{{executions}}</td>
+                <td style="padding: 12px; text-align: right;">{{parse_rate}}%</td>
+                <td style="padding: 12px; text-align: right;">{{valid_rate}}%</td>
+                <td style="padding: 12px;">{{#each failed_paths}}<code>{{this}}</code> {{else}}-{{/each}}</td>
+            </tr>
+            {{/each}}
+        </tbody>
+    </table>
+    <h3 style="margin-top: 20px;">Violations</h3>
+    <table style="width: 100%; border-collapse: collapse;">
+        <thead>
+            <tr style="border-bottom: 2px solid #e2e8f0;">
+                <th style="text-align: left; padding: 12px;">Provider/Model</th>
+                <th style="text-align: left; padding: 12px;">Path</th>
+                <th style="text-align: left; padding: 12px;">Problem</th>
+            </tr>
+        </thead>
+        <tbody>
+            {{#each evaluation.results.analysis.schema_conformance}}
+            {{#each violations}}
+            <tr style="border-bottom: 1px solid #f1f5f9;">
+                <td style="padding: 12px; font-weight: 500;">{{../provider}}/{{../model}}</td>
+                <td style="padding: 12px;"><code>{{path}}</code></td>
+                <td style="padding: 12px;">{{message}}</td>
+            </tr>
+            {{/each}}
+            {{/each}}
+        </tbody>
+    </table>
+</div>
+{{/if}}
+
 <div class="card">
     <h2>Detailed Metrics</h2>
     {{#if evaluation.results}}
@@ -447,6 +814,7 @@ const ANALYSIS_TEMPLATE: &str = r#"{{#*inline "page"}}
             <tr style="border-bottom: 2px solid #e2e8f0;">
                 <th style="text-align: left; padding: 12px;">Provider/Model</th>
                 <th style="text-align: right; padding: 12px;">Response Time</th>
+                <th style="text-align: right; padding: 12px;">TTFT</th>
                 <th style="text-align: right; padding: 12px;">Cost</th>
                 <th style="text-align: right; padding: 12px;">Tokens</th>
                 <th style="text-align: center; padding: 12px;">Status</th>
@@ -457,6 +825,7 @@ const ANALYSIS_TEMPLATE: &str = r#"{{#*inline "page"}}
             <tr style="border-bottom: 1px solid #f1f5f9;">
                 <td style="padding: 12px; font-weight: 500;">{{provider}}/{{model}}</td>
                 <td style="padding: 12px; text-align: right;">{{metadata.response_time_ms}}ms</td>
+                <td style="padding: 12px; text-align: right;">{{#if metadata.time_to_first_token_ms}}{{metadata.time_to_first_token_ms}}ms{{else}}-{{/if}}</td>
                 <td style="padding: 12px; text-align: right;">${{metadata.cost_usd}}</td>
                 <td style="padding: 12px; text-align: right;">{{metadata.token_count_output}}</td>
                 <td style="padding: 12px; text-align: center;">